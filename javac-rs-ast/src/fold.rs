@@ -0,0 +1,303 @@
+//! Owned transformation of AST nodes
+
+use crate::{
+    Annotation, AnnotationTypeDeclaration, AnnotationTypeElement, ElementValue, ElementValuePair,
+    Expression, IdentifierName, Literal, Span, SpannedExpression, Statement, Type,
+};
+
+/// Folder of AST nodes whose methods by default rebuild the node from its folded children.
+///
+/// Implementors override only the methods of the nodes they want to replace
+/// calling the corresponding `fold_*` function to keep transforming the children.
+pub trait Fold {
+    /// Folds an expression rebuilding it from its folded children by default.
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        fold_expression(self, expression)
+    }
+
+    /// Folds an expression along with its span folding both of them by default.
+    fn fold_spanned_expression(&mut self, expression: SpannedExpression) -> SpannedExpression {
+        SpannedExpression {
+            node: self.fold_expression(expression.node),
+            span: self.fold_span(expression.span),
+        }
+    }
+
+    /// Folds a statement rebuilding it from its folded children by default.
+    fn fold_statement(&mut self, statement: Statement) -> Statement {
+        fold_statement(self, statement)
+    }
+
+    /// Folds an annotation type declaration rebuilding it from its folded children by default.
+    fn fold_annotation_type_declaration(
+        &mut self,
+        declaration: AnnotationTypeDeclaration,
+    ) -> AnnotationTypeDeclaration {
+        fold_annotation_type_declaration(self, declaration)
+    }
+
+    /// Folds an annotation rebuilding it from its folded element values by default.
+    fn fold_annotation(&mut self, annotation: Annotation) -> Annotation {
+        fold_annotation(self, annotation)
+    }
+
+    /// Folds an element value rebuilding it from its folded children by default.
+    fn fold_element_value(&mut self, value: ElementValue) -> ElementValue {
+        fold_element_value(self, value)
+    }
+
+    /// Folds a literal keeping it as is by default.
+    fn fold_literal(&mut self, literal: Literal) -> Literal {
+        literal
+    }
+
+    /// Folds an identifier name keeping it as is by default.
+    fn fold_identifier(&mut self, name: IdentifierName) -> IdentifierName {
+        name
+    }
+
+    /// Folds a type keeping it as is by default.
+    fn fold_type(&mut self, java_type: Type) -> Type {
+        java_type
+    }
+
+    /// Folds a span keeping it as is by default.
+    fn fold_span(&mut self, span: Span) -> Span {
+        span
+    }
+}
+
+/// Folder replacing all spans with the default one.
+struct SpanEraser;
+
+impl Fold for SpanEraser {
+    fn fold_span(&mut self, _span: Span) -> Span {
+        Span::default()
+    }
+}
+
+impl Expression {
+    /// Replaces spans of all nested expressions with the default one
+    /// so that the expression may be compared ignoring positions.
+    pub fn without_spans(self) -> Self {
+        SpanEraser.fold_expression(self)
+    }
+}
+
+impl Statement {
+    /// Replaces spans of all nested expressions with the default one
+    /// so that the statement may be compared ignoring positions.
+    pub fn without_spans(self) -> Self {
+        SpanEraser.fold_statement(self)
+    }
+}
+
+impl ElementValue {
+    /// Replaces spans of all nested expressions with the default one
+    /// so that the element value may be compared ignoring positions.
+    pub fn without_spans(self) -> Self {
+        SpanEraser.fold_element_value(self)
+    }
+}
+
+impl AnnotationTypeDeclaration {
+    /// Replaces spans of all nested expressions with the default one
+    /// so that the declaration may be compared ignoring positions.
+    pub fn without_spans(self) -> Self {
+        SpanEraser.fold_annotation_type_declaration(self)
+    }
+}
+
+/// Folds the boxed expression reusing its allocation.
+fn fold_boxed<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut expression: Box<SpannedExpression>,
+) -> Box<SpannedExpression> {
+    *expression = folder.fold_spanned_expression(*expression);
+    expression
+}
+
+/// Rebuilds the given expression from its folded children.
+pub fn fold_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression {
+        Expression::Literal(literal) => Expression::Literal(folder.fold_literal(literal)),
+        Expression::Identifier(name) => Expression::Identifier(folder.fold_identifier(name)),
+        Expression::Unary { op, operand } => Expression::Unary {
+            op,
+            operand: fold_boxed(folder, operand),
+        },
+        Expression::Cast {
+            target_type,
+            operand,
+        } => Expression::Cast {
+            target_type: folder.fold_type(target_type),
+            operand: fold_boxed(folder, operand),
+        },
+        Expression::InstanceOf {
+            operand,
+            target_type,
+        } => Expression::InstanceOf {
+            operand: fold_boxed(folder, operand),
+            target_type: folder.fold_type(target_type),
+        },
+        Expression::ArrayAccess { array, index } => Expression::ArrayAccess {
+            array: fold_boxed(folder, array),
+            index: fold_boxed(folder, index),
+        },
+        Expression::NewArray {
+            element_type,
+            dimensions,
+        } => Expression::NewArray {
+            element_type: folder.fold_type(element_type),
+            dimensions: dimensions
+                .into_iter()
+                .map(|dimension| {
+                    dimension.map(|dimension| folder.fold_spanned_expression(dimension))
+                })
+                .collect(),
+        },
+        Expression::FieldAccess { target, field } => Expression::FieldAccess {
+            target: fold_boxed(folder, target),
+            field: folder.fold_identifier(field),
+        },
+        Expression::MethodInvocation {
+            receiver,
+            name,
+            arguments,
+        } => Expression::MethodInvocation {
+            receiver: receiver.map(|receiver| fold_boxed(folder, receiver)),
+            name: folder.fold_identifier(name),
+            arguments: arguments
+                .into_iter()
+                .map(|argument| folder.fold_spanned_expression(argument))
+                .collect(),
+        },
+        Expression::Binary { op, left, right } => Expression::Binary {
+            op,
+            left: fold_boxed(folder, left),
+            right: fold_boxed(folder, right),
+        },
+        Expression::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        } => Expression::Conditional {
+            condition: fold_boxed(folder, condition),
+            then_branch: fold_boxed(folder, then_branch),
+            else_branch: fold_boxed(folder, else_branch),
+        },
+    }
+}
+
+/// Rebuilds the given statement from its folded children.
+pub fn fold_statement<F: Fold + ?Sized>(folder: &mut F, statement: Statement) -> Statement {
+    match statement {
+        Statement::Block(statements) => Statement::Block(
+            statements
+                .into_iter()
+                .map(|statement| folder.fold_statement(statement))
+                .collect(),
+        ),
+        Statement::LocalVariable {
+            variable_type,
+            name,
+            initializer,
+        } => Statement::LocalVariable {
+            variable_type: folder.fold_type(variable_type),
+            name: folder.fold_identifier(name),
+            initializer: initializer.map(|initializer| folder.fold_spanned_expression(initializer)),
+        },
+        Statement::Expression(expression) => {
+            Statement::Expression(folder.fold_spanned_expression(expression))
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => Statement::If {
+            condition: folder.fold_spanned_expression(condition),
+            then_branch: Box::new(folder.fold_statement(*then_branch)),
+            else_branch: else_branch
+                .map(|else_branch| Box::new(folder.fold_statement(*else_branch))),
+        },
+        Statement::While { condition, body } => Statement::While {
+            condition: folder.fold_spanned_expression(condition),
+            body: Box::new(folder.fold_statement(*body)),
+        },
+        Statement::Return(value) => {
+            Statement::Return(value.map(|value| folder.fold_spanned_expression(value)))
+        }
+        Statement::Break(label) => {
+            Statement::Break(label.map(|label| folder.fold_identifier(label)))
+        }
+        Statement::Continue(label) => {
+            Statement::Continue(label.map(|label| folder.fold_identifier(label)))
+        }
+    }
+}
+
+/// Rebuilds the given annotation type declaration from its folded annotations and elements.
+pub fn fold_annotation_type_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    declaration: AnnotationTypeDeclaration,
+) -> AnnotationTypeDeclaration {
+    AnnotationTypeDeclaration {
+        annotations: declaration
+            .annotations
+            .into_iter()
+            .map(|annotation| folder.fold_annotation(annotation))
+            .collect(),
+        modifiers: declaration.modifiers,
+        name: folder.fold_identifier(declaration.name),
+        elements: declaration
+            .elements
+            .into_iter()
+            .map(|element| AnnotationTypeElement {
+                annotations: element
+                    .annotations
+                    .into_iter()
+                    .map(|annotation| folder.fold_annotation(annotation))
+                    .collect(),
+                modifiers: element.modifiers,
+                element_type: folder.fold_type(element.element_type),
+                name: folder.fold_identifier(element.name),
+                default_value: element
+                    .default_value
+                    .map(|value| folder.fold_element_value(value)),
+            })
+            .collect(),
+    }
+}
+
+/// Rebuilds the given annotation from its folded element values.
+pub fn fold_annotation<F: Fold + ?Sized>(folder: &mut F, annotation: Annotation) -> Annotation {
+    Annotation {
+        type_name: annotation.type_name,
+        elements: annotation
+            .elements
+            .into_iter()
+            .map(|pair| ElementValuePair {
+                name: folder.fold_identifier(pair.name),
+                value: folder.fold_element_value(pair.value),
+            })
+            .collect(),
+    }
+}
+
+/// Rebuilds the given element value from its folded children.
+pub fn fold_element_value<F: Fold + ?Sized>(folder: &mut F, value: ElementValue) -> ElementValue {
+    match value {
+        ElementValue::Expression(expression) => {
+            ElementValue::Expression(folder.fold_spanned_expression(expression))
+        }
+        ElementValue::Array(values) => ElementValue::Array(
+            values
+                .into_iter()
+                .map(|value| folder.fold_element_value(value))
+                .collect(),
+        ),
+        ElementValue::Annotation(annotation) => {
+            ElementValue::Annotation(folder.fold_annotation(annotation))
+        }
+    }
+}
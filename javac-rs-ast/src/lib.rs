@@ -1,10 +1,458 @@
 //! Elements of Java language AST (Abstract Syntax Tree)
 
+pub mod fold;
+pub mod visit;
+
+pub use fold::Fold;
+pub use visit::{walk_expression, Visitor};
+
+/// Name of an identifier.
+pub type IdentifierName = String;
+
+/// Java `char` value which is a single UTF-16 code unit.
+pub type Char = u16;
+
+/// Value of a string literal which is a sequence of UTF-16 code units
+/// possibly containing unpaired surrogates.
+pub type StringLiteralValue = Vec<u16>;
+
+/// Range of byte offsets in the source code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Offset of the first byte
+    pub start: usize,
+    /// Offset following the last byte
+    pub end: usize,
+}
+
+/// AST node along with the span of the source code it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    /// Parsed node
+    pub node: T,
+    /// Span of the node in the source code
+    pub span: Span,
+}
+
+/// Expression along with its span.
+pub type SpannedExpression = Spanned<Expression>;
+
+/// Literal value as specified by JLS 3.10.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// `int` literal, e.g. `123`
+    Int(i32),
+    /// `long` literal, e.g. `123L`
+    Long(i64),
+    /// `float` literal, e.g. `1.5f`
+    Float(f32),
+    /// `double` literal, e.g. `1.5`
+    Double(f64),
+    /// `char` literal, e.g. `'a'`
+    Char(Char),
+    /// String literal, e.g. `"Hello world"`
+    String(StringLiteralValue),
+    /// `boolean` literal, i.e. `true` or `false`
+    Boolean(bool),
+    /// Null literal, i.e. `null`
+    Null,
+}
+
+/// Primitive type as specified by JLS 4.2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimitiveType {
+    /// `boolean`
+    Boolean,
+    /// `byte`
+    Byte,
+    /// `short`
+    Short,
+    /// `int`
+    Int,
+    /// `long`
+    Long,
+    /// `char`
+    Char,
+    /// `float`
+    Float,
+    /// `double`
+    Double,
+}
+
+/// Type as specified by JLS 4.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// Primitive type, e.g. `int`
+    Primitive(PrimitiveType),
+    /// Reference type referred to by its possibly qualified name, e.g. `java.lang.String`
+    Reference(Vec<IdentifierName>),
+    /// Array type with the given component type, e.g. `int[]`
+    Array(Box<Type>),
+}
+
+/// Operator of a unary or a binary expression as specified by JLS 15.15-15.24.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `+`, either unary plus or addition
+    Plus,
+    /// `-`, either unary minus or subtraction
+    Minus,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Remainder,
+    /// `<<`
+    ShiftLeft,
+    /// `>>`
+    ShiftRight,
+    /// `>>>`
+    UnsignedShiftRight,
+    /// `<`
+    Less,
+    /// `>`
+    Greater,
+    /// `<=`
+    LessOrEqual,
+    /// `>=`
+    GreaterOrEqual,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `&`
+    BitwiseAnd,
+    /// `^`
+    BitwiseXor,
+    /// `|`
+    BitwiseOr,
+    /// `&&`
+    LogicalAnd,
+    /// `||`
+    LogicalOr,
+    /// `~`
+    BitwiseComplement,
+    /// `!`
+    LogicalComplement,
+}
+
+/// Java expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// Literal expression, e.g. `"foo"`
+    Literal(Literal),
+    /// Identifier expression, e.g. `foo`
+    Identifier(IdentifierName),
+    /// Unary operator expression, e.g. `-a`
+    Unary {
+        op: Operator,
+        operand: Box<SpannedExpression>,
+    },
+    /// Cast expression, e.g. `(int) foo`
+    Cast {
+        target_type: Type,
+        operand: Box<SpannedExpression>,
+    },
+    /// Type comparison expression, e.g. `foo instanceof String`
+    InstanceOf {
+        operand: Box<SpannedExpression>,
+        target_type: Type,
+    },
+    /// Array access expression, e.g. `foo[i]`
+    ArrayAccess {
+        array: Box<SpannedExpression>,
+        index: Box<SpannedExpression>,
+    },
+    /// Array creation expression, e.g. `new int[n][]`
+    /// with `None` standing for dimensions whose size is not specified
+    NewArray {
+        element_type: Type,
+        dimensions: Vec<Option<SpannedExpression>>,
+    },
+    /// Field access expression, e.g. `foo.bar`
+    FieldAccess {
+        target: Box<SpannedExpression>,
+        field: IdentifierName,
+    },
+    /// Method invocation expression, e.g. `foo(a, b)` or `obj.foo()`
+    MethodInvocation {
+        receiver: Option<Box<SpannedExpression>>,
+        name: IdentifierName,
+        arguments: Vec<SpannedExpression>,
+    },
+    /// Binary operator expression, e.g. `a + b`
+    Binary {
+        op: Operator,
+        left: Box<SpannedExpression>,
+        right: Box<SpannedExpression>,
+    },
+    /// Conditional expression, e.g. `a ? b : c`
+    Conditional {
+        condition: Box<SpannedExpression>,
+        then_branch: Box<SpannedExpression>,
+        else_branch: Box<SpannedExpression>,
+    },
+}
+
+/// Modifier of a declaration as specified by JLS 8.1.1 and 9.1.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    /// `public`
+    Public,
+    /// `protected`
+    Protected,
+    /// `private`
+    Private,
+    /// `abstract`
+    Abstract,
+    /// `static`
+    Static,
+    /// `final`
+    Final,
+    /// `strictfp`
+    Strictfp,
+}
+
+/// Annotation type declaration as specified by JLS 9.6, e.g. `public @interface Foo { int value(); }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationTypeDeclaration {
+    /// Annotations of the declaration, e.g. `@Documented`
+    pub annotations: Vec<Annotation>,
+    /// Modifiers of the declaration other than annotations, e.g. `public`
+    pub modifiers: Vec<Modifier>,
+    /// Simple name of the annotation type
+    pub name: IdentifierName,
+    /// Elements declared by the annotation type
+    pub elements: Vec<AnnotationTypeElement>,
+}
+
+/// Element of an annotation type, e.g. `int value() default 1;`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationTypeElement {
+    /// Annotations of the element, e.g. `@Deprecated`
+    pub annotations: Vec<Annotation>,
+    /// Modifiers of the element other than annotations, e.g. `public`
+    pub modifiers: Vec<Modifier>,
+    /// Type of the element
+    pub element_type: Type,
+    /// Name of the element
+    pub name: IdentifierName,
+    /// Value of the element used when the annotation does not specify one
+    pub default_value: Option<ElementValue>,
+}
+
+/// Annotation as specified by JLS 9.7, e.g. `@Foo(bar = 1)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// Possibly qualified name of the annotation type
+    pub type_name: Vec<IdentifierName>,
+    /// Element-value pairs with the single element annotation, e.g. `@Foo(1)`,
+    /// represented as a pair named `value`
+    pub elements: Vec<ElementValuePair>,
+}
+
+/// Element-value pair of an annotation, e.g. `bar = 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementValuePair {
+    /// Name of the element
+    pub name: IdentifierName,
+    /// Value of the element
+    pub value: ElementValue,
+}
+
+/// Value of an annotation element as specified by JLS 9.7.1.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementValue {
+    /// Conditional expression, e.g. `1 + 2`
+    Expression(SpannedExpression),
+    /// Element-value array initializer, e.g. `{1, 2}`
+    Array(Vec<ElementValue>),
+    /// Nested annotation, e.g. `@Foo`
+    Annotation(Annotation),
+}
+
+/// Java statement as specified by JLS 14.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// Block of statements, e.g. `{ foo(); bar(); }`
+    Block(Vec<Statement>),
+    /// Local variable declaration, e.g. `int x = 1;`
+    LocalVariable {
+        variable_type: Type,
+        name: IdentifierName,
+        initializer: Option<SpannedExpression>,
+    },
+    /// Expression statement, e.g. `foo();`
+    Expression(SpannedExpression),
+    /// Conditional statement, e.g. `if (a) foo(); else bar();`
+    If {
+        condition: SpannedExpression,
+        then_branch: Box<Statement>,
+        else_branch: Option<Box<Statement>>,
+    },
+    /// Loop statement, e.g. `while (a) foo();`
+    While {
+        condition: SpannedExpression,
+        body: Box<Statement>,
+    },
+    /// Return statement with an optional value, e.g. `return 1;`
+    Return(Option<SpannedExpression>),
+    /// Break statement with an optional label, e.g. `break outer;`
+    Break(Option<IdentifierName>),
+    /// Continue statement with an optional label, e.g. `continue outer;`
+    Continue(Option<IdentifierName>),
+}
+
 #[allow(clippy::panic)]
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    fn unspanned(node: Expression) -> SpannedExpression {
+        Spanned {
+            node,
+            span: Span::default(),
+        }
+    }
+
+    fn int(value: i32) -> Expression {
+        Expression::Literal(Literal::Int(value))
+    }
+
+    fn identifier(name: &str) -> Expression {
+        Expression::Identifier(name.to_string())
+    }
+
+    fn binary(op: Operator, left: Expression, right: Expression) -> Expression {
+        Expression::Binary {
+            op,
+            left: Box::new(unspanned(left)),
+            right: Box::new(unspanned(right)),
+        }
+    }
+
+    /// Visitor counting literal nodes.
+    #[derive(Default)]
+    struct LiteralCounter {
+        count: usize,
+    }
+
+    impl Visitor for LiteralCounter {
+        fn visit_literal(&mut self, _literal: &Literal) {
+            self.count += 1;
+        }
+    }
+
+    #[test]
+    fn visitor_counts_literals() {
+        // 1 + foo(2, a) * (3 - 4)
+        let expression = binary(
+            Operator::Plus,
+            int(1),
+            binary(
+                Operator::Multiply,
+                Expression::MethodInvocation {
+                    receiver: None,
+                    name: "foo".to_string(),
+                    arguments: vec![unspanned(int(2)), unspanned(identifier("a"))],
+                },
+                binary(Operator::Minus, int(3), int(4)),
+            ),
+        );
+
+        let mut counter = LiteralCounter::default();
+        counter.visit_expression(&expression);
+        assert_eq!(counter.count, 4);
+    }
+
+    /// Visitor collecting spans of identifiers.
+    #[derive(Default)]
+    struct IdentifierSpans {
+        spans: Vec<Span>,
+    }
+
+    impl Visitor for IdentifierSpans {
+        fn visit_spanned_expression(&mut self, expression: &SpannedExpression) {
+            if let Expression::Identifier(_) = expression.node {
+                self.spans.push(expression.span);
+            }
+            self.visit_expression(&expression.node);
+        }
+    }
+
+    #[test]
+    fn visitor_sees_spans() {
+        // a - -b
+        let expression = Expression::Binary {
+            op: Operator::Minus,
+            left: Box::new(Spanned {
+                node: identifier("a"),
+                span: Span { start: 0, end: 1 },
+            }),
+            right: Box::new(Spanned {
+                node: Expression::Unary {
+                    op: Operator::Minus,
+                    operand: Box::new(Spanned {
+                        node: identifier("b"),
+                        span: Span { start: 5, end: 6 },
+                    }),
+                },
+                span: Span { start: 4, end: 6 },
+            }),
+        };
+
+        let mut visitor = IdentifierSpans::default();
+        visitor.visit_expression(&expression);
+        assert_eq!(
+            visitor.spans,
+            vec![Span { start: 0, end: 1 }, Span { start: 5, end: 6 }]
+        );
+        assert_eq!(
+            expression.without_spans(),
+            Expression::Binary {
+                op: Operator::Minus,
+                left: Box::new(unspanned(identifier("a"))),
+                right: Box::new(unspanned(Expression::Unary {
+                    op: Operator::Minus,
+                    operand: Box::new(unspanned(identifier("b"))),
+                })),
+            }
+        );
+    }
+
+    /// Folder negating all `int` literals.
+    struct IntNegator;
+
+    impl Fold for IntNegator {
+        fn fold_literal(&mut self, literal: Literal) -> Literal {
+            match literal {
+                Literal::Int(value) => Literal::Int(-value),
+                literal => literal,
+            }
+        }
+    }
+
+    #[test]
+    fn fold_rebuilds_expression() {
+        let expression = Expression::Conditional {
+            condition: Box::new(unspanned(identifier("a"))),
+            then_branch: Box::new(unspanned(binary(Operator::Plus, int(1), int(2)))),
+            else_branch: Box::new(unspanned(Expression::Literal(Literal::Boolean(true)))),
+        };
+
+        assert_eq!(
+            IntNegator.fold_expression(expression),
+            Expression::Conditional {
+                condition: Box::new(unspanned(identifier("a"))),
+                then_branch: Box::new(unspanned(binary(Operator::Plus, int(-1), int(-2)))),
+                else_branch: Box::new(unspanned(Expression::Literal(Literal::Boolean(true)))),
+            }
+        );
+    }
 }
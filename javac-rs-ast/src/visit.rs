@@ -0,0 +1,147 @@
+//! Read-only traversal of AST nodes
+
+use crate::{Expression, IdentifierName, Literal, SpannedExpression, Statement, Type};
+
+/// Visitor of AST nodes whose methods by default walk into the children of the visited node.
+///
+/// Implementors override only the methods of the nodes they are interested in
+/// calling the corresponding `walk_*` function to continue the traversal.
+pub trait Visitor {
+    /// Visits an expression walking into its children by default.
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    /// Visits an expression along with its span visiting the expression itself by default.
+    fn visit_spanned_expression(&mut self, expression: &SpannedExpression) {
+        self.visit_expression(&expression.node);
+    }
+
+    /// Visits a statement walking into its children by default.
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    /// Visits a literal.
+    fn visit_literal(&mut self, _literal: &Literal) {}
+
+    /// Visits an identifier name, e.g. of a variable, field or method.
+    fn visit_identifier(&mut self, _name: &IdentifierName) {}
+
+    /// Visits a type.
+    fn visit_type(&mut self, _java_type: &Type) {}
+}
+
+/// Visits all children of the given expression.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+        Expression::Identifier(name) => visitor.visit_identifier(name),
+        Expression::Unary { operand, .. } => visitor.visit_spanned_expression(operand),
+        Expression::Cast {
+            target_type,
+            operand,
+        } => {
+            visitor.visit_type(target_type);
+            visitor.visit_spanned_expression(operand);
+        }
+        Expression::InstanceOf {
+            operand,
+            target_type,
+        } => {
+            visitor.visit_spanned_expression(operand);
+            visitor.visit_type(target_type);
+        }
+        Expression::ArrayAccess { array, index } => {
+            visitor.visit_spanned_expression(array);
+            visitor.visit_spanned_expression(index);
+        }
+        Expression::NewArray {
+            element_type,
+            dimensions,
+        } => {
+            visitor.visit_type(element_type);
+            for dimension in dimensions.iter().flatten() {
+                visitor.visit_spanned_expression(dimension);
+            }
+        }
+        Expression::FieldAccess { target, field } => {
+            visitor.visit_spanned_expression(target);
+            visitor.visit_identifier(field);
+        }
+        Expression::MethodInvocation {
+            receiver,
+            name,
+            arguments,
+        } => {
+            if let Some(receiver) = receiver {
+                visitor.visit_spanned_expression(receiver);
+            }
+            visitor.visit_identifier(name);
+            for argument in arguments {
+                visitor.visit_spanned_expression(argument);
+            }
+        }
+        Expression::Binary { left, right, .. } => {
+            visitor.visit_spanned_expression(left);
+            visitor.visit_spanned_expression(right);
+        }
+        Expression::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_spanned_expression(condition);
+            visitor.visit_spanned_expression(then_branch);
+            visitor.visit_spanned_expression(else_branch);
+        }
+    }
+}
+
+/// Visits all children of the given statement.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Block(statements) => {
+            for statement in statements {
+                visitor.visit_statement(statement);
+            }
+        }
+        Statement::LocalVariable {
+            variable_type,
+            name,
+            initializer,
+        } => {
+            visitor.visit_type(variable_type);
+            visitor.visit_identifier(name);
+            if let Some(initializer) = initializer {
+                visitor.visit_spanned_expression(initializer);
+            }
+        }
+        Statement::Expression(expression) => visitor.visit_spanned_expression(expression),
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_spanned_expression(condition);
+            visitor.visit_statement(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_statement(else_branch);
+            }
+        }
+        Statement::While { condition, body } => {
+            visitor.visit_spanned_expression(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::Return(value) => {
+            if let Some(value) = value {
+                visitor.visit_spanned_expression(value);
+            }
+        }
+        Statement::Break(label) | Statement::Continue(label) => {
+            if let Some(label) = label {
+                visitor.visit_identifier(label);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Errors produced by the parser
+
+use peg::str::LineCol;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Error describing why and where parsing of Java source code failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    offset: usize,
+    expected: Vec<&'static str>,
+}
+
+impl ParseError {
+    /// Gets the 1-based line at which the error happened.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the 1-based column at which the error happened.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Gets the 0-based byte offset from the start of the source at which the error happened.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Gets the tokens any of which would have allowed the parser to advance, sorted alphabetically.
+    pub fn expected(&self) -> &[&'static str] {
+        &self.expected
+    }
+}
+
+impl From<peg::error::ParseError<LineCol>> for ParseError {
+    fn from(error: peg::error::ParseError<LineCol>) -> Self {
+        let mut expected = error.expected.tokens().collect::<Vec<_>>();
+        expected.sort_unstable();
+
+        Self {
+            line: error.location.line,
+            column: error.location.column,
+            offset: error.location.offset,
+            expected,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "error at {}:{}: expected ",
+            self.line, self.column
+        )?;
+        match self.expected.as_slice() {
+            [] => write!(formatter, "<unreported>"),
+            [token] => write!(formatter, "{}", token),
+            [first, rest @ ..] => {
+                write!(formatter, "one of {}", first)?;
+                rest.iter()
+                    .try_for_each(|token| write!(formatter, ", {}", token))
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
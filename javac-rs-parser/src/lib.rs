@@ -1,10 +1,65 @@
 //! Utilities for parsing Java language AST (Abstract Syntax Tree)
 
+pub mod error;
+pub mod parser;
+
+pub use error::ParseError;
+use javac_rs_ast as ast;
+
+/// Parses the given source as a single Java expression.
+///
+/// # Errors
+///
+/// Returns [`ParseError`] describing the position at which the source is not a valid expression.
+pub fn parse_expression(source: &str) -> Result<ast::Expression, ParseError> {
+    parser::java::expression(source).map_err(ParseError::from)
+}
+
 #[allow(clippy::panic)]
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn parse_expression_success() {
+        assert_eq!(
+            parse_expression("a"),
+            Ok(ast::Expression::Identifier("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_expression_error_position() {
+        let error = parse_expression("1 +\n  * 2").unwrap_err();
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 3);
+        assert_eq!(error.offset(), 6);
+        assert!(error.expected().contains(&"identifier"));
+    }
+
+    #[test]
+    fn parse_expression_error_expecting_keyword() {
+        let error = parse_expression("a instanceofB").unwrap_err();
+        assert_eq!(error.offset(), 2);
+        assert!(error.expected().contains(&"instanceof"));
+    }
+
+    #[test]
+    fn parse_expression_error_at_start() {
+        let error = parse_expression(")").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 1));
+    }
+
+    #[test]
+    fn parse_error_display() {
+        let error = parse_expression("f(").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("error at 1:3: expected one of "));
+    }
 }
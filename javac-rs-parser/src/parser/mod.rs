@@ -0,0 +1,2911 @@
+//! Parser of Java language source code
+
+use javac_rs_ast as ast;
+
+mod numeric;
+mod text_block;
+
+fn unary(op: ast::Operator, operand: ast::SpannedExpression) -> ast::Expression {
+    ast::Expression::Unary {
+        op,
+        operand: Box::new(operand),
+    }
+}
+
+fn binary(
+    op: ast::Operator,
+    left: ast::SpannedExpression,
+    right: ast::SpannedExpression,
+) -> ast::Expression {
+    ast::Expression::Binary {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+fn cast(target_type: ast::Type, operand: ast::SpannedExpression) -> ast::Expression {
+    ast::Expression::Cast {
+        target_type,
+        operand: Box::new(operand),
+    }
+}
+
+/// Modifier of a declaration which may be either an annotation or a keyword.
+enum DeclarationModifier {
+    Annotation(ast::Annotation),
+    Keyword(ast::Modifier),
+}
+
+/// Error reported when a char literal contains no characters or more than one.
+const CHAR_LITERAL_LENGTH_ERROR: &str = "char literal containing exactly one character";
+
+peg::parser! {
+    /// Grammar of Java language as specified by JLS se15.
+    pub grammar java() for str {
+        use peg::ParseLiteral;
+        use unicode_xid::UnicodeXID;
+
+        // Annotation type declarations (JLS 9.6)
+
+        /// Annotation type declaration, e.g. `public @interface Foo { int value() default 1; }`.
+        pub rule annotation_type_declaration() -> ast::AnnotationTypeDeclaration
+            = __ modifiers:modifiers() "@" __ word("interface") __
+            name:identifier_name() __ "{" __ elements:(annotation_type_element() ** __) __ "}" __ {
+                let (annotations, modifiers) = modifiers;
+                ast::AnnotationTypeDeclaration { annotations, modifiers, name, elements }
+            }
+
+        /// Element of an annotation type, e.g. `String name() default "foo";`.
+        rule annotation_type_element() -> ast::AnnotationTypeElement
+            = modifiers:modifiers()
+            element_type:java_type() __ name:identifier_name() __ "(" __ ")"
+            default_value:(__ word("default") __ value:element_value() { value })? __ ";" {
+                let (annotations, modifiers) = modifiers;
+                ast::AnnotationTypeElement { annotations, modifiers, element_type, name, default_value }
+            }
+
+        // Annotations (JLS 9.7)
+
+        /// Annotation, e.g. `@Foo`, `@Foo(1)` or `@Foo(bar = 1, baz = {})`.
+        rule annotation() -> ast::Annotation
+            = "@" __ type_name:type_name()
+            elements:(__ "(" __ elements:annotation_elements() __ ")" { elements })? {
+                ast::Annotation { type_name, elements: elements.unwrap_or_default() }
+            }
+
+        rule annotation_elements() -> Vec<ast::ElementValuePair>
+            = element_value_pair() ++ (__ "," __)
+            / value:element_value() {
+                vec![ast::ElementValuePair { name: "value".to_string(), value }]
+            }
+            / { vec![] }
+
+        rule element_value_pair() -> ast::ElementValuePair
+            = name:identifier_name() __ "=" !"=" __ value:element_value() {
+                ast::ElementValuePair { name, value }
+            }
+
+        /// Value of an annotation element, e.g. `1`, `{1, 2}` or `@Foo`.
+        rule element_value() -> ast::ElementValue
+            = "{" __ values:(element_value() ** (__ "," __)) __ ","? __ "}" {
+                ast::ElementValue::Array(values)
+            }
+            / annotation:annotation() { ast::ElementValue::Annotation(annotation) }
+            / expression:operator_expression() { ast::ElementValue::Expression(expression) }
+
+        /// Annotations and other modifiers of a declaration in any order, e.g. `@Foo public @Bar`.
+        rule modifiers() -> (Vec<ast::Annotation>, Vec<ast::Modifier>)
+            = modifiers:(modifier:(
+                annotation:annotation() { DeclarationModifier::Annotation(annotation) }
+                / modifier:modifier() { DeclarationModifier::Keyword(modifier) }
+            ) __ { modifier })* {
+                let mut annotations = Vec::new();
+                let mut keywords = Vec::new();
+                for modifier in modifiers {
+                    match modifier {
+                        DeclarationModifier::Annotation(annotation) => annotations.push(annotation),
+                        DeclarationModifier::Keyword(modifier) => keywords.push(modifier),
+                    }
+                }
+                (annotations, keywords)
+            }
+
+        rule modifier() -> ast::Modifier
+            = word("public") { ast::Modifier::Public }
+            / word("protected") { ast::Modifier::Protected }
+            / word("private") { ast::Modifier::Private }
+            / word("abstract") { ast::Modifier::Abstract }
+            / word("static") { ast::Modifier::Static }
+            / word("final") { ast::Modifier::Final }
+            / word("strictfp") { ast::Modifier::Strictfp }
+
+        // Statements (JLS 14)
+
+        /// Statement of any kind.
+        pub rule statement() -> ast::Statement = __ statement:statement_without_padding() __ { statement }
+
+        /// Statement of any kind along with its span excluding the surrounding whitespace and comments.
+        pub rule spanned_statement() -> ast::Spanned<ast::Statement>
+            = __ node:spanned(<statement_without_padding()>) __ { node }
+
+        rule statement_without_padding() -> ast::Statement
+            = block_statement()
+            / if_statement()
+            / while_statement()
+            / return_statement()
+            / break_statement()
+            / continue_statement()
+            / local_variable_statement()
+            / expression_statement()
+
+        /// Block of statements, e.g. `{ int a = 1; foo(a); }`.
+        rule block_statement() -> ast::Statement
+            = "{" __ statements:(statement_without_padding() ** __) __ "}" {
+                ast::Statement::Block(statements)
+            }
+
+        /// Conditional statement, e.g. `if (a) foo(); else bar();`.
+        ///
+        /// Dangling `else` belongs to the innermost `if` as required by JLS 14.5.
+        rule if_statement() -> ast::Statement
+            = word("if") __ "(" __ condition:operator_expression() __ ")" __ then_branch:statement_without_padding()
+            else_branch:(__ word("else") __ else_branch:statement_without_padding() { else_branch })? {
+                ast::Statement::If {
+                    condition,
+                    then_branch: Box::new(then_branch),
+                    else_branch: else_branch.map(Box::new),
+                }
+            }
+
+        /// Loop statement, e.g. `while (a) foo();`.
+        rule while_statement() -> ast::Statement
+            = word("while") __ "(" __ condition:operator_expression() __ ")" __ body:statement_without_padding() {
+                ast::Statement::While { condition, body: Box::new(body) }
+            }
+
+        /// Return statement, e.g. `return;` or `return a + 1;`.
+        rule return_statement() -> ast::Statement
+            = word("return") value:(__ value:operator_expression() { value })? __ ";" {
+                ast::Statement::Return(value)
+            }
+
+        /// Break statement, e.g. `break;` or `break outer;`.
+        rule break_statement() -> ast::Statement
+            = word("break") label:(__ label:identifier_name() { label })? __ ";" {
+                ast::Statement::Break(label)
+            }
+
+        /// Continue statement, e.g. `continue;` or `continue outer;`.
+        rule continue_statement() -> ast::Statement
+            = word("continue") label:(__ label:identifier_name() { label })? __ ";" {
+                ast::Statement::Continue(label)
+            }
+
+        /// Declaration of a single local variable, e.g. `int x = 1 + 2;`.
+        rule local_variable_statement() -> ast::Statement
+            = variable_type:java_type() __ name:identifier_name()
+            initializer:(__ "=" !"=" __ initializer:operator_expression() { initializer })? __ ";" {
+                ast::Statement::LocalVariable { variable_type, name, initializer }
+            }
+
+        /// Expression statement which may only be a method invocation
+        /// as the other statement expressions of JLS 14.8 are not yet supported.
+        rule expression_statement() -> ast::Statement
+            = expression:operator_expression() __ ";" {?
+                match expression.node {
+                    ast::Expression::MethodInvocation { .. } => Ok(ast::Statement::Expression(expression)),
+                    _ => Err("statement expression"),
+                }
+            }
+
+        /// Expression of any kind.
+        pub rule expression() -> ast::Expression
+            = __ expression:operator_expression() __ { expression.node }
+
+        /// Expression of any kind along with its span excluding the surrounding whitespace and comments.
+        pub rule spanned_expression() -> ast::SpannedExpression
+            = __ expression:operator_expression() __ { expression }
+
+        rule spanned<T>(node: rule<T>) -> ast::Spanned<T>
+            = start:position!() node:node() end:position!() {
+                ast::Spanned { node, span: ast::Span { start, end } }
+            }
+
+        /// Expression of any kind with spans of it and of all its operands.
+        rule operator_expression() -> ast::SpannedExpression = precedence!{
+            start:position!() node:@ end:position!() {
+                ast::Spanned { node, span: ast::Span { start, end } }
+            }
+            --
+            condition:@ __ "?" __ then_branch:operator_expression() __ ":" __ else_branch:(@) {
+                ast::Expression::Conditional {
+                    condition: Box::new(condition),
+                    then_branch: Box::new(then_branch),
+                    else_branch: Box::new(else_branch),
+                }
+            }
+            --
+            left:(@) __ "||" __ right:@ { binary(ast::Operator::LogicalOr, left, right) }
+            --
+            left:(@) __ "&&" __ right:@ { binary(ast::Operator::LogicalAnd, left, right) }
+            --
+            left:(@) __ "|" !['|' | '='] __ right:@ { binary(ast::Operator::BitwiseOr, left, right) }
+            --
+            left:(@) __ "^" !"=" __ right:@ { binary(ast::Operator::BitwiseXor, left, right) }
+            --
+            left:(@) __ "&" !['&' | '='] __ right:@ { binary(ast::Operator::BitwiseAnd, left, right) }
+            --
+            left:(@) __ "==" __ right:@ { binary(ast::Operator::Equal, left, right) }
+            left:(@) __ "!=" __ right:@ { binary(ast::Operator::NotEqual, left, right) }
+            --
+            left:(@) __ "<=" __ right:@ { binary(ast::Operator::LessOrEqual, left, right) }
+            left:(@) __ ">=" __ right:@ { binary(ast::Operator::GreaterOrEqual, left, right) }
+            left:(@) __ "<" !['<' | '='] __ right:@ { binary(ast::Operator::Less, left, right) }
+            left:(@) __ ">" !['>' | '='] __ right:@ { binary(ast::Operator::Greater, left, right) }
+            operand:(@) __ word("instanceof") __ target_type:reference_type() {
+                ast::Expression::InstanceOf { operand: Box::new(operand), target_type }
+            }
+            --
+            left:(@) __ "<<" !"=" __ right:@ { binary(ast::Operator::ShiftLeft, left, right) }
+            left:(@) __ ">>>" !"=" __ right:@ { binary(ast::Operator::UnsignedShiftRight, left, right) }
+            left:(@) __ ">>" !['>' | '='] __ right:@ { binary(ast::Operator::ShiftRight, left, right) }
+            --
+            left:(@) __ "+" !['+' | '='] __ right:@ { binary(ast::Operator::Plus, left, right) }
+            left:(@) __ "-" !['-' | '='] __ right:@ { binary(ast::Operator::Minus, left, right) }
+            --
+            left:(@) __ "*" !"=" __ right:@ { binary(ast::Operator::Multiply, left, right) }
+            left:(@) __ "/" !"=" __ right:@ { binary(ast::Operator::Divide, left, right) }
+            left:(@) __ "%" !"=" __ right:@ { binary(ast::Operator::Remainder, left, right) }
+            --
+            "+" !['+' | '='] __ operand:@ { unary(ast::Operator::Plus, operand) }
+            "-" !['-' | '='] __ operand:@ { unary(ast::Operator::Minus, operand) }
+            "~" __ operand:@ { unary(ast::Operator::BitwiseComplement, operand) }
+            "!" !"=" __ operand:@ { unary(ast::Operator::LogicalComplement, operand) }
+            // As specified by JLS 15.16, a parenthesized primitive type is always a cast
+            // while any other parenthesized type is only one if not followed by `+` or `-`
+            // so that `(x) - y` is a subtraction rather than a cast of `-y` to type `x`
+            "(" __ primitive:primitive_type() __ ")" __ operand:@ {
+                cast(ast::Type::Primitive(primitive), operand)
+            }
+            "(" __ target_type:java_type() __ ")" __ !['+' | '-'] operand:@ {
+                cast(target_type, operand)
+            }
+            --
+            array:@ __ "[" __ index:operator_expression() __ "]" {
+                ast::Expression::ArrayAccess { array: Box::new(array), index: Box::new(index) }
+            }
+            receiver:@ __ "." __ name:identifier_name() __ arguments:arguments() {
+                ast::Expression::MethodInvocation { receiver: Some(Box::new(receiver)), name, arguments }
+            }
+            target:@ __ "." __ field:identifier_name() {
+                ast::Expression::FieldAccess { target: Box::new(target), field }
+            }
+            --
+            // JLS 3.10.1 allows the magnitudes of `int` and `long` minimal values only under unary minus
+            "-" __ value:negated_min_value_literal() { value }
+            expression:primary_expression() { expression }
+        }
+
+        /// Expression which is not built of operators.
+        rule primary_expression() -> ast::Expression
+            = literal_expression()
+            / new_array_expression()
+            / method_invocation_expression()
+            / identifier_expression()
+            / "(" __ expression:operator_expression() __ ")" { expression.node }
+
+        /// Parenthesized comma-separated arguments of a method invocation.
+        rule arguments() -> Vec<ast::SpannedExpression>
+            = "(" __ arguments:(operator_expression() ** (__ "," __)) __ ")" { arguments }
+
+        /// Invocation of a method without an explicit receiver, e.g. `foo(a, b)`.
+        pub rule method_invocation_expression() -> ast::Expression
+            = name:identifier_name() __ arguments:arguments() {
+                ast::Expression::MethodInvocation { receiver: None, name, arguments }
+            }
+
+        /// Array creation expression, e.g. `new int[3][]`.
+        pub rule new_array_expression() -> ast::Expression
+            = word("new") __ element_type:(
+                primitive:primitive_type() { ast::Type::Primitive(primitive) }
+                / name:type_name() { ast::Type::Reference(name) }
+            ) __ sized:("[" __ size:operator_expression() __ "]" { Some(size) }) ++ __
+            empty:(__ "[" __ "]" { None })* {
+                ast::Expression::NewArray {
+                    element_type,
+                    dimensions: sized.into_iter().chain(empty).collect(),
+                }
+            }
+
+        /// Literal expression of any kind.
+        pub rule literal_expression() -> ast::Expression
+            = float_literal_expression()
+            / double_literal_expression()
+            / long_literal_expression()
+            / int_literal_expression()
+            / char_literal_expression()
+            / text_block_expression()
+            / string_literal_expression()
+            / boolean_literal_expression()
+            / null_literal_expression()
+
+        // Line terminators (JLS 3.4)
+
+        rule line_terminator() = "\r\n" / "\n" / "\r"
+
+        // White space (JLS 3.6)
+
+        rule _() = quiet!{[' ' | '\t' | '\x0C' | '\r' | '\n']*}
+
+        /// White space and comments which may appear between any tokens.
+        rule __() = quiet!{_ (comment() _)*}
+
+        // Comments (JLS 3.7)
+
+        rule multiline_comment() = "/*" (!"*/" [_])* "*/"
+
+        rule line_comment() = "//" (!['\r' | '\n'] [_])*
+
+        rule comment() = multiline_comment() / line_comment()
+
+        // Identifiers (JLS 3.8)
+
+        /// Symbol for which `Character.isJavaIdentifierStart(char)` is `true`.
+        rule first_identifier_symbol() = [c if c.is_xid_start() || c == '$' || c == '_']
+
+        /// Symbol for which `Character.isJavaIdentifierPart(char)` is `true`.
+        rule identifier_symbol() = [c if c.is_xid_continue() || c == '$']
+
+        /// Identifier which is not a keyword or a reserved literal, e.g. `foo`.
+        rule identifier_name() -> ast::IdentifierName
+            = quiet!{
+                !(keyword() / reserved_literal()) name:$(first_identifier_symbol() identifier_symbol()*) {
+                    name.to_string()
+                }
+            } / expected!("identifier")
+
+        /// Identifier expression, e.g. `foo`.
+        pub rule identifier_expression() -> ast::Expression
+            = name:identifier_name() { ast::Expression::Identifier(name) }
+
+        // Keywords (JLS 3.9)
+
+        /// Exactly the given word not followed by any identifier symbol.
+        ///
+        /// The word itself is reported as expected on failure since `expected!` only accepts literals.
+        rule word(name: &'static str)
+            = quiet!{##parse_string_literal(name) !identifier_symbol()} / {? Err(name) }
+
+        rule keyword()
+            = word("abstract") / word("assert") / word("boolean") / word("break") / word("byte")
+            / word("case") / word("catch") / word("char") / word("class") / word("const")
+            / word("continue") / word("default") / word("do") / word("double") / word("else")
+            / word("enum") / word("extends") / word("final") / word("finally") / word("float")
+            / word("for") / word("if") / word("goto") / word("implements") / word("import")
+            / word("instanceof") / word("int") / word("interface") / word("long") / word("native")
+            / word("new") / word("package") / word("private") / word("protected") / word("public")
+            / word("return") / word("short") / word("static") / word("strictfp") / word("super")
+            / word("switch") / word("synchronized") / word("this") / word("throw") / word("throws")
+            / word("transient") / word("try") / word("void") / word("volatile") / word("while")
+            / word("_")
+
+        /// Literals which may not be used as identifiers.
+        rule reserved_literal() = boolean_value() / null()
+
+        // Types (JLS 4)
+
+        rule primitive_type() -> ast::PrimitiveType
+            = word("boolean") { ast::PrimitiveType::Boolean }
+            / word("byte") { ast::PrimitiveType::Byte }
+            / word("short") { ast::PrimitiveType::Short }
+            / word("int") { ast::PrimitiveType::Int }
+            / word("long") { ast::PrimitiveType::Long }
+            / word("char") { ast::PrimitiveType::Char }
+            / word("float") { ast::PrimitiveType::Float }
+            / word("double") { ast::PrimitiveType::Double }
+
+        /// Possibly qualified name of a type, e.g. `java.lang.String`.
+        rule type_name() -> Vec<ast::IdentifierName> = identifier_name() ++ (__ "." __)
+
+        /// Primitive, reference or array type, e.g. `int`, `String` or `int[][]`.
+        rule java_type() -> ast::Type
+            = element_type:(
+                primitive:primitive_type() { ast::Type::Primitive(primitive) }
+                / name:type_name() { ast::Type::Reference(name) }
+            ) dimensions:(__ "[" __ "]")* {
+                dimensions
+                    .iter()
+                    .fold(element_type, |component, _| ast::Type::Array(Box::new(component)))
+            }
+
+        /// Reference or array type, e.g. `String` or `int[]`.
+        rule reference_type() -> ast::Type
+            = java_type:java_type() {?
+                match java_type {
+                    ast::Type::Primitive(_) => Err("reference type"),
+                    java_type => Ok(java_type),
+                }
+            }
+
+        // Integer literals (JLS 3.10.1)
+
+        rule digit() = ['0'..='9']
+
+        rule hex_digit() = ['0'..='9' | 'a'..='f' | 'A'..='F']
+
+        rule octal_digit() = ['0'..='7']
+
+        rule binary_digit() = ['0' | '1']
+
+        // Underscores are only allowed strictly between two digits
+
+        rule digits() = digit() ("_"* digit())*
+
+        rule hex_digits() = hex_digit() ("_"* hex_digit())*
+
+        rule binary_digits() = binary_digit() ("_"* binary_digit())*
+
+        /// Digits of an integer numeral with its radix.
+        rule integer_numeral() -> (&'input str, u32)
+            = "0" ['x' | 'X'] digits:$(hex_digits()) { (digits, 16) }
+            / "0" ['b' | 'B'] digits:$(binary_digits()) { (digits, 2) }
+            / "0" digits:$(("_"* octal_digit())+) { (digits, 8) }
+            / digits:$("0" / ['1'..='9'] ("_"* digit())*) { (digits, 10) }
+
+        rule long_suffix() = ['l' | 'L']
+
+        /// `int` literal, e.g. `123`, `0x7F`, `017` or `0b1010`.
+        pub rule int_literal_expression() -> ast::Expression
+            = numeral:integer_numeral() !identifier_symbol() {?
+                numeric::int_value(numeral.0, numeral.1)
+                    .map(|value| ast::Expression::Literal(ast::Literal::Int(value)))
+                    .ok_or("int literal")
+            }
+
+        /// `long` literal, e.g. `123L`.
+        pub rule long_literal_expression() -> ast::Expression
+            = numeral:integer_numeral() long_suffix() !identifier_symbol() {?
+                numeric::long_value(numeral.0, numeral.1)
+                    .map(|value| ast::Expression::Literal(ast::Literal::Long(value)))
+                    .ok_or("long literal")
+            }
+
+        /// Decimal magnitude of `i32::MIN` or `i64::MIN` folded with the preceding unary minus.
+        rule negated_min_value_literal() -> ast::Expression
+            = digits:$(['1'..='9'] ("_"* digit())*) suffix:$(long_suffix())? !identifier_symbol() {?
+                match suffix {
+                    None if numeric::is_int_min_magnitude(digits) => {
+                        Ok(ast::Expression::Literal(ast::Literal::Int(i32::MIN)))
+                    }
+                    Some(_) if numeric::is_long_min_magnitude(digits) => {
+                        Ok(ast::Expression::Literal(ast::Literal::Long(i64::MIN)))
+                    }
+                    _ => Err("minimal integer value"),
+                }
+            }
+
+        // Floating-point literals (JLS 3.10.2)
+
+        rule exponent_part() = ['e' | 'E'] ['+' | '-']? digits()
+
+        /// Floating-point number which is not an integer numeral, without the type suffix.
+        rule floating_point_number() -> numeric::FloatingPoint<'input>
+            = "0" ['x' | 'X']
+                significand:$(hex_digits()? "." hex_digits() / hex_digits() "."?)
+                ['p' | 'P'] exponent:$(['+' | '-']? digits()) {
+                numeric::FloatingPoint::Hexadecimal { significand, exponent }
+            }
+            / number:$(
+                digits() "." digits()? exponent_part()?
+                / "." digits() exponent_part()?
+                / digits() exponent_part()
+            ) { numeric::FloatingPoint::Decimal(number) }
+
+        /// Floating-point number which may require the type suffix to be one, without the suffix.
+        rule suffixed_floating_point_number() -> numeric::FloatingPoint<'input>
+            = floating_point_number()
+            / number:$(digits()) { numeric::FloatingPoint::Decimal(number) }
+
+        /// `float` literal, e.g. `1.5f`.
+        pub rule float_literal_expression() -> ast::Expression
+            = number:suffixed_floating_point_number() ['f' | 'F'] !identifier_symbol() {?
+                number
+                    .to_f32()
+                    .map(|value| ast::Expression::Literal(ast::Literal::Float(value)))
+                    .ok_or("float literal")
+            }
+
+        /// `double` literal, e.g. `1.5` or `1e10d`.
+        pub rule double_literal_expression() -> ast::Expression
+            = number:(
+                number:suffixed_floating_point_number() ['d' | 'D'] { number }
+                / floating_point_number()
+            ) !identifier_symbol() {?
+                number
+                    .to_f64()
+                    .map(|value| ast::Expression::Literal(ast::Literal::Double(value)))
+                    .ok_or("double literal")
+            }
+
+        // Text blocks (JLS 3.10.6)
+
+        rule text_block_delimiter() = "\"\"\""
+
+        rule text_block_character() = !text_block_delimiter() ("\\" [_] / [_])
+
+        /// Text block delimited by `"""`, with incidental white space stripped and all escapes decoded.
+        pub rule text_block_expression() -> ast::Expression
+            = text_block_delimiter() [' ' | '\t' | '\x0C']* line_terminator()
+            content:$(text_block_character()*) text_block_delimiter() {?
+                text_block::translate_escapes(&text_block::strip_indent(content))
+                    .map(|value| ast::Expression::Literal(ast::Literal::String(value)))
+                    .ok_or("valid text block")
+            }
+
+        // Escapes (JLS 3.3 and JLS 3.10.7)
+
+        /// Escape sequence mapped to the character it denotes.
+        rule escape_sequence() -> u16
+            = "\\" value:(
+                "b" { 0x08 }
+                / "t" { 0x09 }
+                / "n" { 0x0A }
+                / "f" { 0x0C }
+                / "r" { 0x0D }
+                / "s" { 0x20 }
+                / "\"" { 0x22 }
+                / "'" { 0x27 }
+                / "\\" { 0x5C }
+            ) { value }
+
+        /// Unicode escape, e.g. `\u00E9`, yielding a single UTF-16 code unit.
+        rule unicode_escape() -> u16
+            = "\\" "u"+ digits:$(hex_digit()*<4>) {?
+                u16::from_str_radix(digits, 16).or(Err("unicode escape"))
+            }
+
+        /// Octal escape, e.g. `\0` or `\377`.
+        rule octal_escape() -> u16
+            = "\\" digits:$(['0'..='3'] octal_digit() octal_digit() / octal_digit() octal_digit()?) {?
+                u16::from_str_radix(digits, 8).or(Err("octal escape"))
+            }
+
+        rule escaped_code_unit() -> u16
+            = escape_sequence() / unicode_escape() / octal_escape()
+
+        // Boolean literals (JLS 3.10.3)
+
+        rule boolean_value() -> bool = word("true") { true } / word("false") { false }
+
+        /// Boolean literal, i.e. `true` or `false`.
+        pub rule boolean_literal_expression() -> ast::Expression
+            = value:boolean_value() { ast::Expression::Literal(ast::Literal::Boolean(value)) }
+
+        // Character literals (JLS 3.10.4)
+
+        rule single_character() -> ast::Char
+            = symbol:$(!['\'' | '\\' | '\r' | '\n'] [_]) {?
+                match *symbol.encode_utf16().collect::<Vec<_>>() {
+                    [unit] => Ok(unit),
+                    _ => Err("single UTF-16 code unit"),
+                }
+            }
+
+        /// Value of a char literal which is always a single UTF-16 code unit
+        /// so supplementary characters cannot be written even as a surrogate pair.
+        rule character_value() -> ast::Char = single_character() / escaped_code_unit()
+
+        /// Character literal, e.g. `'a'` or `'\n'`.
+        pub rule char_literal_expression() -> ast::Expression
+            = "'" values:character_value()* "'" {?
+                match *values {
+                    [value] => Ok(ast::Expression::Literal(ast::Literal::Char(value))),
+                    _ => Err(CHAR_LITERAL_LENGTH_ERROR),
+                }
+            }
+
+        // String literals (JLS 3.10.5)
+
+        rule string_character() = !['"' | '\\' | '\r' | '\n'] [_]
+
+        rule string_literal_part() -> Vec<u16>
+            = raw:$(string_character()+) { raw.encode_utf16().collect() }
+            / unit:escaped_code_unit() { vec![unit] }
+
+        /// String literal, e.g. `"Hello\tworld"`, with all escapes decoded.
+        pub rule string_literal_expression() -> ast::Expression
+            = "\"" parts:string_literal_part()* "\"" {
+                ast::Expression::Literal(ast::Literal::String(parts.concat()))
+            }
+
+        // Null literal (JLS 3.10.8)
+
+        rule null() = word("null")
+
+        /// Null literal, i.e. `null`.
+        pub rule null_literal_expression() -> ast::Expression
+            = null() { ast::Expression::Literal(ast::Literal::Null) }
+    }
+}
+
+#[allow(clippy::panic)]
+#[cfg(test)]
+mod tests {
+    use super::CHAR_LITERAL_LENGTH_ERROR;
+    use javac_rs_ast as ast;
+
+    /// Rules of the grammar with spans of the parsed expressions erased
+    /// so that the expected trees may be built without positions.
+    mod java {
+        use javac_rs_ast as ast;
+        use peg::{error::ParseError, str::LineCol};
+
+        pub use super::super::java::{
+            boolean_literal_expression, char_literal_expression, double_literal_expression,
+            float_literal_expression, identifier_expression, int_literal_expression,
+            literal_expression, long_literal_expression, null_literal_expression,
+            spanned_expression, spanned_statement, string_literal_expression,
+            text_block_expression,
+        };
+
+        type Result<T> = std::result::Result<T, ParseError<LineCol>>;
+
+        pub fn expression(source: &str) -> Result<ast::Expression> {
+            super::super::java::expression(source).map(ast::Expression::without_spans)
+        }
+
+        pub fn method_invocation_expression(source: &str) -> Result<ast::Expression> {
+            super::super::java::method_invocation_expression(source)
+                .map(ast::Expression::without_spans)
+        }
+
+        pub fn new_array_expression(source: &str) -> Result<ast::Expression> {
+            super::super::java::new_array_expression(source).map(ast::Expression::without_spans)
+        }
+
+        pub fn statement(source: &str) -> Result<ast::Statement> {
+            super::super::java::statement(source).map(ast::Statement::without_spans)
+        }
+
+        pub fn annotation_type_declaration(source: &str) -> Result<ast::AnnotationTypeDeclaration> {
+            super::super::java::annotation_type_declaration(source)
+                .map(ast::AnnotationTypeDeclaration::without_spans)
+        }
+    }
+
+    fn unspanned(node: ast::Expression) -> ast::SpannedExpression {
+        ast::Spanned {
+            node,
+            span: ast::Span::default(),
+        }
+    }
+
+    fn int(value: i32) -> ast::Expression {
+        ast::Expression::Literal(ast::Literal::Int(value))
+    }
+
+    fn long(value: i64) -> ast::Expression {
+        ast::Expression::Literal(ast::Literal::Long(value))
+    }
+
+    fn float(value: f32) -> ast::Expression {
+        ast::Expression::Literal(ast::Literal::Float(value))
+    }
+
+    fn double(value: f64) -> ast::Expression {
+        ast::Expression::Literal(ast::Literal::Double(value))
+    }
+
+    fn char(value: char) -> ast::Expression {
+        let mut buffer = [0; 2];
+        match *value.encode_utf16(&mut buffer) {
+            [unit] => ast::Expression::Literal(ast::Literal::Char(unit)),
+            _ => panic!("{:?} is not a single UTF-16 code unit", value),
+        }
+    }
+
+    fn string(value: &str) -> ast::Expression {
+        ast::Expression::Literal(ast::Literal::String(value.encode_utf16().collect()))
+    }
+
+    fn identifier(name: &str) -> ast::Expression {
+        ast::Expression::Identifier(name.to_string())
+    }
+
+    fn invocation(
+        receiver: Option<ast::Expression>,
+        name: &str,
+        arguments: Vec<ast::Expression>,
+    ) -> ast::Expression {
+        ast::Expression::MethodInvocation {
+            receiver: receiver.map(|receiver| Box::new(unspanned(receiver))),
+            name: name.to_string(),
+            arguments: arguments.into_iter().map(unspanned).collect(),
+        }
+    }
+
+    fn array_access(array: ast::Expression, index: ast::Expression) -> ast::Expression {
+        ast::Expression::ArrayAccess {
+            array: Box::new(unspanned(array)),
+            index: Box::new(unspanned(index)),
+        }
+    }
+
+    fn conditional(
+        condition: ast::Expression,
+        then_branch: ast::Expression,
+        else_branch: ast::Expression,
+    ) -> ast::Expression {
+        ast::Expression::Conditional {
+            condition: Box::new(unspanned(condition)),
+            then_branch: Box::new(unspanned(then_branch)),
+            else_branch: Box::new(unspanned(else_branch)),
+        }
+    }
+
+    fn field_access(target: ast::Expression, field: &str) -> ast::Expression {
+        ast::Expression::FieldAccess {
+            target: Box::new(unspanned(target)),
+            field: field.to_string(),
+        }
+    }
+
+    fn cast(target_type: ast::Type, operand: ast::Expression) -> ast::Expression {
+        super::cast(target_type, unspanned(operand))
+    }
+
+    fn reference(name: &str) -> ast::Type {
+        ast::Type::Reference(name.split('.').map(str::to_string).collect())
+    }
+
+    fn local_variable(
+        variable_type: ast::Type,
+        name: &str,
+        initializer: Option<ast::Expression>,
+    ) -> ast::Statement {
+        ast::Statement::LocalVariable {
+            variable_type,
+            name: name.to_string(),
+            initializer: initializer.map(unspanned),
+        }
+    }
+
+    fn unary(op: ast::Operator, operand: ast::Expression) -> ast::Expression {
+        super::unary(op, unspanned(operand))
+    }
+
+    fn binary(op: ast::Operator, left: ast::Expression, right: ast::Expression) -> ast::Expression {
+        super::binary(op, unspanned(left), unspanned(right))
+    }
+
+    mod int {
+        use super::*;
+
+        #[test]
+        fn decimal() {
+            assert_eq!(java::int_literal_expression("0"), Ok(int(0)));
+            assert_eq!(java::int_literal_expression("123"), Ok(int(123)));
+            assert_eq!(
+                java::int_literal_expression("2147483647"),
+                Ok(int(i32::MAX))
+            );
+        }
+
+        #[test]
+        fn hex() {
+            assert_eq!(java::int_literal_expression("0x7F"), Ok(int(0x7F)));
+            assert_eq!(java::int_literal_expression("0XcafE"), Ok(int(0xCAFE)));
+            assert_eq!(java::int_literal_expression("0xFFFFFFFF"), Ok(int(-1)));
+        }
+
+        #[test]
+        fn octal() {
+            assert_eq!(java::int_literal_expression("017"), Ok(int(0o17)));
+            assert_eq!(java::int_literal_expression("0_17"), Ok(int(0o17)));
+            assert_eq!(java::int_literal_expression("037777777777"), Ok(int(-1)));
+        }
+
+        #[test]
+        fn binary() {
+            assert_eq!(java::int_literal_expression("0b1010"), Ok(int(0b1010)));
+            assert_eq!(java::int_literal_expression("0B1_0"), Ok(int(0b10)));
+        }
+
+        #[test]
+        fn underscores() {
+            assert_eq!(
+                java::int_literal_expression("1_000_000"),
+                Ok(int(1_000_000))
+            );
+            assert_eq!(java::int_literal_expression("1__2"), Ok(int(12)));
+            assert_eq!(java::int_literal_expression("0xF_F"), Ok(int(0xFF)));
+        }
+
+        #[test]
+        fn misplaced_underscores() {
+            assert!(java::int_literal_expression("_123").is_err());
+            assert!(java::int_literal_expression("123_").is_err());
+            assert!(java::int_literal_expression("0x_FF").is_err());
+            assert!(java::int_literal_expression("0xFF_").is_err());
+            assert!(java::int_literal_expression("0b_1").is_err());
+            assert!(java::int_literal_expression("017_").is_err());
+        }
+
+        #[test]
+        fn invalid_digits() {
+            assert!(java::int_literal_expression("09").is_err());
+            assert!(java::int_literal_expression("0b2").is_err());
+            assert!(java::int_literal_expression("0x").is_err());
+            assert!(java::int_literal_expression("12a").is_err());
+        }
+
+        #[test]
+        fn too_large() {
+            assert!(java::int_literal_expression("2147483648").is_err());
+            assert!(java::int_literal_expression("2147483649").is_err());
+            assert!(java::int_literal_expression("0x100000000").is_err());
+        }
+    }
+
+    mod long {
+        use super::*;
+
+        #[test]
+        fn decimal() {
+            assert_eq!(java::long_literal_expression("0L"), Ok(long(0)));
+            assert_eq!(java::long_literal_expression("123l"), Ok(long(123)));
+            assert_eq!(
+                java::long_literal_expression("9223372036854775807L"),
+                Ok(long(i64::MAX))
+            );
+        }
+
+        #[test]
+        fn non_decimal() {
+            assert_eq!(
+                java::long_literal_expression("0xFFFFFFFFFFFFFFFFL"),
+                Ok(long(-1))
+            );
+            assert_eq!(java::long_literal_expression("017L"), Ok(long(0o17)));
+            assert_eq!(java::long_literal_expression("0b11L"), Ok(long(0b11)));
+        }
+
+        #[test]
+        fn underscores() {
+            assert_eq!(
+                java::long_literal_expression("1_000_000L"),
+                Ok(long(1_000_000))
+            );
+        }
+
+        #[test]
+        fn misplaced_underscores() {
+            assert!(java::long_literal_expression("_123L").is_err());
+            assert!(java::long_literal_expression("123_L").is_err());
+            assert!(java::long_literal_expression("0x_FFL").is_err());
+        }
+
+        #[test]
+        fn missing_suffix() {
+            assert!(java::long_literal_expression("123").is_err());
+        }
+
+        #[test]
+        fn too_large() {
+            assert!(java::long_literal_expression("9223372036854775808L").is_err());
+            assert!(java::long_literal_expression("0x10000000000000000L").is_err());
+        }
+    }
+
+    mod float {
+        use super::*;
+
+        #[test]
+        fn decimal() {
+            assert_eq!(java::float_literal_expression("1.5f"), Ok(float(1.5)));
+            assert_eq!(java::float_literal_expression("1.F"), Ok(float(1.0)));
+            assert_eq!(java::float_literal_expression(".5f"), Ok(float(0.5)));
+            assert_eq!(java::float_literal_expression("1e3f"), Ok(float(1e3)));
+            assert_eq!(java::float_literal_expression("2f"), Ok(float(2.0)));
+            assert_eq!(
+                java::float_literal_expression("3.4028235e38f"),
+                Ok(float(f32::MAX))
+            );
+        }
+
+        #[test]
+        fn hex() {
+            assert_eq!(java::float_literal_expression("0x1.8p1f"), Ok(float(3.0)));
+            assert_eq!(java::float_literal_expression("0x.8p0f"), Ok(float(0.5)));
+            assert_eq!(
+                java::float_literal_expression("0x1.fffffeP+127f"),
+                Ok(float(f32::MAX))
+            );
+            assert_eq!(
+                java::float_literal_expression("0x0.000002P-126f"),
+                Ok(float(f32::from_bits(1)))
+            );
+        }
+
+        #[test]
+        fn underscores() {
+            assert_eq!(
+                java::float_literal_expression("1_000.0_1f"),
+                Ok(float(1000.01))
+            );
+        }
+
+        #[test]
+        fn misplaced_underscores() {
+            assert!(java::float_literal_expression("1._5f").is_err());
+            assert!(java::float_literal_expression("1_.5f").is_err());
+            assert!(java::float_literal_expression("1.5_f").is_err());
+            assert!(java::float_literal_expression("1e_5f").is_err());
+        }
+
+        #[test]
+        fn out_of_range() {
+            assert!(java::float_literal_expression("3.5e38f").is_err());
+            assert!(java::float_literal_expression("1e-50f").is_err());
+            assert!(java::float_literal_expression("0x1p128f").is_err());
+        }
+
+        #[test]
+        fn zero() {
+            assert_eq!(java::float_literal_expression("0.0f"), Ok(float(0.0)));
+            assert_eq!(java::float_literal_expression("0e99f"), Ok(float(0.0)));
+        }
+    }
+
+    mod double {
+        use super::*;
+
+        #[test]
+        fn decimal() {
+            assert_eq!(java::double_literal_expression("1.5"), Ok(double(1.5)));
+            assert_eq!(java::double_literal_expression("1."), Ok(double(1.0)));
+            assert_eq!(java::double_literal_expression(".5"), Ok(double(0.5)));
+            assert_eq!(java::double_literal_expression("1e3"), Ok(double(1e3)));
+            assert_eq!(java::double_literal_expression("2d"), Ok(double(2.0)));
+            assert_eq!(java::double_literal_expression("2.5D"), Ok(double(2.5)));
+            assert_eq!(java::double_literal_expression("09.5"), Ok(double(9.5)));
+        }
+
+        #[test]
+        fn hex() {
+            assert_eq!(java::double_literal_expression("0x1.8p1"), Ok(double(3.0)));
+            assert_eq!(
+                java::double_literal_expression("0x1.fffffffffffffP+1023"),
+                Ok(double(f64::MAX))
+            );
+            assert_eq!(
+                java::double_literal_expression("0x0.0000000000001P-1022"),
+                Ok(double(f64::from_bits(1)))
+            );
+            assert_eq!(
+                java::double_literal_expression("0x1.00000000000008p0"),
+                Ok(double(1.0))
+            );
+            assert_eq!(
+                java::double_literal_expression("0x1.00000000000018p0"),
+                Ok(double(1.0 + 2.0 * f64::EPSILON))
+            );
+        }
+
+        #[test]
+        fn hex_significand() {
+            assert_eq!(java::double_literal_expression("0xA.p1"), Ok(double(20.0)));
+            assert_eq!(java::double_literal_expression("0x.Bp1"), Ok(double(1.375)));
+            assert_eq!(
+                java::double_literal_expression("0xA.Bp1"),
+                Ok(double(21.375))
+            );
+            assert_eq!(java::double_literal_expression("0xAp1"), Ok(double(20.0)));
+        }
+
+        #[test]
+        fn empty_hex_significand() {
+            assert!(java::double_literal_expression("0x.p1").is_err());
+            assert!(java::double_literal_expression("0xp1").is_err());
+            assert!(java::float_literal_expression("0x.p1f").is_err());
+        }
+
+        #[test]
+        fn underscores() {
+            assert_eq!(
+                java::double_literal_expression("1_000_000.5"),
+                Ok(double(1_000_000.5))
+            );
+        }
+
+        #[test]
+        fn misplaced_underscores() {
+            assert!(java::double_literal_expression("1._5").is_err());
+            assert!(java::double_literal_expression("1_.5").is_err());
+            assert!(java::double_literal_expression("_1.5").is_err());
+            assert!(java::double_literal_expression("1.5_").is_err());
+            assert!(java::double_literal_expression("0x_1p1").is_err());
+        }
+
+        #[test]
+        fn not_floating_point() {
+            assert!(java::double_literal_expression("1").is_err());
+            assert!(java::double_literal_expression("1.5f").is_err());
+        }
+
+        #[test]
+        fn out_of_range() {
+            assert!(java::double_literal_expression("1e309").is_err());
+            assert!(java::double_literal_expression("1e-400").is_err());
+            assert!(java::double_literal_expression("0x1p-1080").is_err());
+        }
+    }
+
+    mod char {
+        use super::*;
+
+        #[test]
+        fn simple() {
+            assert_eq!(java::char_literal_expression("'a'"), Ok(char('a')));
+            assert_eq!(java::char_literal_expression("'\"'"), Ok(char('"')));
+            assert_eq!(java::char_literal_expression("'ж'"), Ok(char('ж')));
+        }
+
+        #[test]
+        fn escape_sequences() {
+            assert_eq!(java::char_literal_expression(r"'\n'"), Ok(char('\n')));
+            assert_eq!(java::char_literal_expression(r"'\''"), Ok(char('\'')));
+            assert_eq!(java::char_literal_expression(r"'\\'"), Ok(char('\\')));
+        }
+
+        #[test]
+        fn unicode_escape() {
+            assert_eq!(java::char_literal_expression(r"'\u0436'"), Ok(char('ж')));
+        }
+
+        #[test]
+        fn octal_escapes() {
+            assert_eq!(java::char_literal_expression(r"'\0'"), Ok(char('\0')));
+            assert_eq!(java::char_literal_expression(r"'\377'"), Ok(char('\u{FF}')));
+        }
+
+        #[test]
+        fn out_of_range_octal_escape() {
+            assert!(java::char_literal_expression(r"'\400'").is_err());
+        }
+
+        fn assert_length_error(source: &str) {
+            let error = java::char_literal_expression(source).unwrap_err();
+            assert_eq!(error.location.offset, source.len());
+            assert!(error
+                .expected
+                .tokens()
+                .any(|token| token == CHAR_LITERAL_LENGTH_ERROR));
+        }
+
+        #[test]
+        fn empty() {
+            assert_length_error("''");
+        }
+
+        #[test]
+        fn multiple_characters() {
+            assert_length_error("'ab'");
+            assert_length_error(r"'\n\n'");
+        }
+
+        #[test]
+        fn lone_surrogate() {
+            assert_eq!(
+                java::char_literal_expression(r"'\uD800'"),
+                Ok(ast::Expression::Literal(ast::Literal::Char(0xD800)))
+            );
+            assert_eq!(
+                java::char_literal_expression(r"'\uDFFF'"),
+                Ok(ast::Expression::Literal(ast::Literal::Char(0xDFFF)))
+            );
+        }
+
+        #[test]
+        fn high_surrogate() {
+            assert_eq!(
+                java::char_literal_expression(r"'\uD83D'"),
+                Ok(ast::Expression::Literal(ast::Literal::Char(0xD83D)))
+            );
+        }
+
+        #[test]
+        fn surrogate_pair() {
+            assert_length_error(r"'\uD83D\uDE00'");
+            assert!(java::char_literal_expression("'😀'").is_err());
+        }
+
+        #[test]
+        fn unescaped() {
+            assert!(java::char_literal_expression("'''").is_err());
+            assert!(java::char_literal_expression("'\n'").is_err());
+        }
+    }
+
+    mod string {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            assert_eq!(java::string_literal_expression(r#""""#), Ok(string("")));
+        }
+
+        #[test]
+        fn simple() {
+            assert_eq!(
+                java::string_literal_expression(r#""Hello world""#),
+                Ok(string("Hello world"))
+            );
+        }
+
+        #[test]
+        fn non_ascii() {
+            assert_eq!(
+                java::string_literal_expression(r#""Привет, 世界 😀""#),
+                Ok(string("Привет, 世界 😀"))
+            );
+        }
+
+        #[test]
+        fn escape_sequences() {
+            assert_eq!(
+                java::string_literal_expression(r#""a\tb""#),
+                Ok(string("a\tb"))
+            );
+            assert_eq!(
+                java::string_literal_expression(r#""\b\t\n\f\r\"\'\\""#),
+                Ok(string("\x08\t\n\x0C\r\"'\\"))
+            );
+            assert_eq!(
+                java::string_literal_expression(r#""a\sb""#),
+                Ok(string("a b"))
+            );
+        }
+
+        #[test]
+        fn unicode_escapes() {
+            assert_eq!(
+                java::string_literal_expression(r#""caf\u00E9 \uuu0041""#),
+                Ok(string("café A"))
+            );
+            assert_eq!(
+                java::string_literal_expression(r#""\uD83D\uDE00""#),
+                Ok(string("😀"))
+            );
+        }
+
+        #[test]
+        fn octal_escapes() {
+            assert_eq!(
+                java::string_literal_expression(r#""\0\7\12\101\377""#),
+                Ok(string("\0\x07\n\u{41}\u{FF}"))
+            );
+            // `\477` is `\47` followed by `7`
+            assert_eq!(
+                java::string_literal_expression(r#""\477""#),
+                Ok(string("'7"))
+            );
+        }
+
+        #[test]
+        fn escaped_backslash_before_u() {
+            assert_eq!(
+                java::string_literal_expression(r#""\\u0041""#),
+                Ok(string("\\u0041"))
+            );
+        }
+
+        #[test]
+        fn unterminated() {
+            assert!(java::string_literal_expression(r#""abc"#).is_err());
+            assert!(java::string_literal_expression(r#""abc\""#).is_err());
+        }
+
+        #[test]
+        fn line_terminator_inside() {
+            assert!(java::string_literal_expression("\"a\nb\"").is_err());
+            assert!(java::string_literal_expression("\"a\rb\"").is_err());
+        }
+
+        #[test]
+        fn invalid_escape() {
+            assert!(java::string_literal_expression(r#""\q""#).is_err());
+            assert!(java::string_literal_expression(r#""\u00""#).is_err());
+        }
+
+        #[test]
+        fn lone_surrogate() {
+            assert_eq!(
+                java::string_literal_expression(r#""\uD83D""#),
+                Ok(ast::Expression::Literal(ast::Literal::String(vec![0xD83D])))
+            );
+            assert_eq!(
+                java::string_literal_expression(r#""a\uD800\uD800""#),
+                Ok(ast::Expression::Literal(ast::Literal::String(vec![
+                    0x61, 0xD800, 0xD800
+                ])))
+            );
+        }
+    }
+
+    mod text_block {
+        use super::*;
+
+        #[test]
+        fn two_lines() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\nHello\nworld\"\"\""),
+                Ok(string("Hello\nworld"))
+            );
+        }
+
+        #[test]
+        fn closing_delimiter_on_separate_line() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n    Hello\n    world\n    \"\"\""),
+                Ok(string("Hello\nworld\n"))
+            );
+        }
+
+        #[test]
+        fn lone_surrogate() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n\\uDC00\"\"\""),
+                Ok(ast::Expression::Literal(ast::Literal::String(vec![0xDC00])))
+            );
+        }
+
+        #[test]
+        fn indentation_stripping() {
+            assert_eq!(
+                java::text_block_expression(
+                    "\"\"\"  \r\n        <p>\n          text  \n\n        </p>\n      \"\"\""
+                ),
+                Ok(string("  <p>\n    text\n\n  </p>\n"))
+            );
+        }
+
+        #[test]
+        fn closing_delimiter_beyond_content() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n    a\n      b\n        \"\"\""),
+                Ok(string("a\n  b\n"))
+            );
+        }
+
+        #[test]
+        fn line_joiner() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n    one \\\n    two\n    \"\"\""),
+                Ok(string("one two\n"))
+            );
+        }
+
+        #[test]
+        fn escapes() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n  a\\s\\s\n  \\\"\"\"\\t\\101\\u0042\"\"\""),
+                Ok(string("a  \n\"\"\"\tAB"))
+            );
+        }
+
+        #[test]
+        fn quotes_inside() {
+            assert_eq!(
+                java::text_block_expression("\"\"\"\n\"a\" \"\"b\"\n\"\"\""),
+                Ok(string("\"a\" \"\"b\"\n"))
+            );
+        }
+
+        #[test]
+        fn missing_line_terminator_after_opening_delimiter() {
+            assert!(java::text_block_expression("\"\"\"Hello\"\"\"").is_err());
+        }
+
+        #[test]
+        fn unterminated() {
+            assert!(java::text_block_expression("\"\"\"\nHello\"\"").is_err());
+        }
+
+        #[test]
+        fn invalid_escape() {
+            assert!(java::text_block_expression("\"\"\"\n\\q\"\"\"").is_err());
+        }
+    }
+
+    mod literal_expression {
+        use super::*;
+
+        #[test]
+        fn numeric_literals() {
+            assert_eq!(java::literal_expression("1"), Ok(int(1)));
+            assert_eq!(java::literal_expression("1L"), Ok(long(1)));
+            assert_eq!(java::literal_expression("1f"), Ok(float(1.0)));
+            assert_eq!(java::literal_expression("1.0"), Ok(double(1.0)));
+            assert_eq!(java::literal_expression("1e1"), Ok(double(10.0)));
+        }
+
+        #[test]
+        fn char_literal() {
+            assert_eq!(java::literal_expression("'c'"), Ok(char('c')));
+        }
+
+        #[test]
+        fn string_literal() {
+            assert_eq!(java::literal_expression(r#""hi""#), Ok(string("hi")));
+        }
+
+        #[test]
+        fn quotes_of_other_kind() {
+            assert_eq!(java::literal_expression(r#""'a'""#), Ok(string("'a'")));
+            assert_eq!(java::literal_expression(r#"'"'"#), Ok(char('"')));
+        }
+
+        #[test]
+        fn text_block() {
+            assert_eq!(
+                java::literal_expression("\"\"\"\nhi\"\"\""),
+                Ok(string("hi"))
+            );
+        }
+    }
+
+    mod boolean_and_null {
+        use super::*;
+
+        #[test]
+        fn boolean() {
+            assert_eq!(
+                java::boolean_literal_expression("true"),
+                Ok(ast::Expression::Literal(ast::Literal::Boolean(true)))
+            );
+            assert_eq!(
+                java::literal_expression("false"),
+                Ok(ast::Expression::Literal(ast::Literal::Boolean(false)))
+            );
+        }
+
+        #[test]
+        fn null() {
+            assert_eq!(
+                java::null_literal_expression("null"),
+                Ok(ast::Expression::Literal(ast::Literal::Null))
+            );
+            assert_eq!(
+                java::literal_expression("null"),
+                Ok(ast::Expression::Literal(ast::Literal::Null))
+            );
+        }
+
+        #[test]
+        fn keyword_boundary() {
+            assert!(java::boolean_literal_expression("trueish").is_err());
+            assert!(java::boolean_literal_expression("falsey").is_err());
+            assert!(java::null_literal_expression("nullable").is_err());
+        }
+
+        #[test]
+        fn identifiers_starting_with_literals() {
+            assert_eq!(java::expression("trueish"), Ok(identifier("trueish")));
+            assert_eq!(java::expression("falsey"), Ok(identifier("falsey")));
+            assert_eq!(java::expression("nullable"), Ok(identifier("nullable")));
+        }
+
+        #[test]
+        fn in_expression() {
+            assert_eq!(
+                java::expression("a == null || !false"),
+                Ok(binary(
+                    ast::Operator::LogicalOr,
+                    binary(
+                        ast::Operator::Equal,
+                        identifier("a"),
+                        ast::Expression::Literal(ast::Literal::Null)
+                    ),
+                    unary(
+                        ast::Operator::LogicalComplement,
+                        ast::Expression::Literal(ast::Literal::Boolean(false))
+                    )
+                ))
+            );
+        }
+    }
+
+    mod identifier {
+        use super::*;
+
+        #[test]
+        fn simple() {
+            assert_eq!(java::identifier_expression("foo"), Ok(identifier("foo")));
+            assert_eq!(
+                java::identifier_expression("_$bar42"),
+                Ok(identifier("_$bar42"))
+            );
+        }
+
+        #[test]
+        fn accented_latin() {
+            assert_eq!(java::identifier_expression("café"), Ok(identifier("café")));
+            assert_eq!(
+                java::identifier_expression("naïve"),
+                Ok(identifier("naïve"))
+            );
+        }
+
+        #[test]
+        fn greek() {
+            assert_eq!(java::identifier_expression("π"), Ok(identifier("π")));
+            assert_eq!(
+                java::identifier_expression("αβγ_1"),
+                Ok(identifier("αβγ_1"))
+            );
+        }
+
+        #[test]
+        fn cjk() {
+            assert_eq!(java::identifier_expression("变量"), Ok(identifier("变量")));
+            assert_eq!(
+                java::identifier_expression("名前$"),
+                Ok(identifier("名前$"))
+            );
+        }
+
+        #[test]
+        fn non_identifier_symbols() {
+            assert!(java::identifier_expression("😀").is_err());
+            assert!(java::identifier_expression("a-b").is_err());
+            assert!(java::identifier_expression("·a").is_err());
+        }
+
+        #[test]
+        fn keyword_followed_by_non_ascii() {
+            assert_eq!(java::identifier_expression("ifé"), Ok(identifier("ifé")));
+        }
+
+        #[test]
+        fn keyword_prefixed() {
+            assert_eq!(java::identifier_expression("iffy"), Ok(identifier("iffy")));
+            assert_eq!(
+                java::identifier_expression("nullx"),
+                Ok(identifier("nullx"))
+            );
+        }
+
+        #[test]
+        fn keyword() {
+            assert!(java::identifier_expression("if").is_err());
+            assert!(java::identifier_expression("_").is_err());
+            assert!(java::identifier_expression("null").is_err());
+        }
+
+        #[test]
+        fn starting_with_digit() {
+            assert!(java::identifier_expression("1abc").is_err());
+        }
+    }
+
+    mod span {
+        use super::*;
+
+        fn at(node: ast::Expression, start: usize, end: usize) -> ast::SpannedExpression {
+            ast::Spanned {
+                node,
+                span: ast::Span { start, end },
+            }
+        }
+
+        fn text<'a>(source: &'a str, expression: &ast::SpannedExpression) -> &'a str {
+            &source[expression.span.start..expression.span.end]
+        }
+
+        #[test]
+        fn binary_expression() {
+            assert_eq!(
+                java::spanned_expression("1 + 2"),
+                Ok(at(
+                    super::super::binary(ast::Operator::Plus, at(int(1), 0, 1), at(int(2), 4, 5)),
+                    0,
+                    5
+                ))
+            );
+        }
+
+        #[test]
+        fn nested_operands() {
+            let source = "(a + b) * -c";
+            let spanned = java::spanned_expression(source).unwrap();
+            match spanned.node {
+                ast::Expression::Binary { left, right, .. } => {
+                    assert_eq!(text(source, &left), "(a + b)");
+                    match left.node {
+                        ast::Expression::Binary { left, right, .. } => {
+                            assert_eq!(left.span, ast::Span { start: 1, end: 2 });
+                            assert_eq!(right.span, ast::Span { start: 5, end: 6 });
+                        }
+                        node => panic!("unexpected left operand {:?}", node),
+                    }
+                    assert_eq!(text(source, &right), "-c");
+                    match right.node {
+                        ast::Expression::Unary { operand, .. } => {
+                            assert_eq!(text(source, &operand), "c")
+                        }
+                        node => panic!("unexpected right operand {:?}", node),
+                    }
+                }
+                node => panic!("unexpected expression {:?}", node),
+            }
+        }
+
+        #[test]
+        fn method_arguments() {
+            let source = "foo.bar(a, /* b */ b + c)[i]";
+            let spanned = java::spanned_expression(source).unwrap();
+            assert_eq!(text(source, &spanned), source);
+            match spanned.node {
+                ast::Expression::ArrayAccess { array, index } => {
+                    assert_eq!(text(source, &index), "i");
+                    assert_eq!(text(source, &array), "foo.bar(a, /* b */ b + c)");
+                    match array.node {
+                        ast::Expression::MethodInvocation {
+                            receiver,
+                            arguments,
+                            ..
+                        } => {
+                            assert_eq!(text(source, &receiver.unwrap()), "foo");
+                            let arguments = arguments
+                                .iter()
+                                .map(|argument| text(source, argument))
+                                .collect::<Vec<_>>();
+                            assert_eq!(arguments, vec!["a", "b + c"]);
+                        }
+                        node => panic!("unexpected array {:?}", node),
+                    }
+                }
+                node => panic!("unexpected expression {:?}", node),
+            }
+        }
+
+        #[test]
+        fn conditional_branches() {
+            let source = "a ? b : c";
+            match java::spanned_expression(source).unwrap().node {
+                ast::Expression::Conditional {
+                    condition,
+                    then_branch,
+                    else_branch,
+                } => {
+                    assert_eq!(condition.span, ast::Span { start: 0, end: 1 });
+                    assert_eq!(then_branch.span, ast::Span { start: 4, end: 5 });
+                    assert_eq!(else_branch.span, ast::Span { start: 8, end: 9 });
+                }
+                node => panic!("unexpected expression {:?}", node),
+            }
+        }
+
+        #[test]
+        fn surrounding_whitespace_excluded() {
+            let spanned = java::spanned_expression(" /* a */ foo ( ) // b\n").unwrap();
+            assert_eq!(spanned.node, invocation(None, "foo", vec![]));
+            assert_eq!(spanned.span, ast::Span { start: 9, end: 16 });
+        }
+
+        #[test]
+        fn statement() {
+            let source = "\n  if ( x > 1 ) return x;\n";
+            let spanned = java::spanned_statement(source).unwrap();
+            assert_eq!(
+                &source[spanned.span.start..spanned.span.end],
+                "if ( x > 1 ) return x;"
+            );
+            match spanned.node {
+                ast::Statement::If {
+                    condition,
+                    then_branch,
+                    ..
+                } => {
+                    assert_eq!(text(source, &condition), "x > 1");
+                    match *then_branch {
+                        ast::Statement::Return(Some(value)) => {
+                            assert_eq!(text(source, &value), "x")
+                        }
+                        statement => panic!("unexpected statement {:?}", statement),
+                    }
+                }
+                statement => panic!("unexpected statement {:?}", statement),
+            }
+        }
+    }
+
+    mod expression {
+        use super::*;
+        use ast::Operator;
+
+        #[test]
+        fn precedence() {
+            assert_eq!(
+                java::expression("1 + 2 * 3"),
+                Ok(binary(
+                    Operator::Plus,
+                    int(1),
+                    binary(Operator::Multiply, int(2), int(3))
+                ))
+            );
+        }
+
+        #[test]
+        fn left_associativity() {
+            assert_eq!(
+                java::expression("a - b - c"),
+                Ok(binary(
+                    Operator::Minus,
+                    binary(Operator::Minus, identifier("a"), identifier("b")),
+                    identifier("c")
+                ))
+            );
+        }
+
+        #[test]
+        fn unary_minus() {
+            assert_eq!(
+                java::expression("-a - -b"),
+                Ok(binary(
+                    Operator::Minus,
+                    unary(Operator::Minus, identifier("a")),
+                    unary(Operator::Minus, identifier("b"))
+                ))
+            );
+        }
+
+        #[test]
+        fn negated_min_values() {
+            assert_eq!(java::expression("-2147483648"), Ok(int(i32::MIN)));
+            assert_eq!(java::expression("-2_147_483_648"), Ok(int(i32::MIN)));
+            assert_eq!(
+                java::expression("- 9223372036854775808L"),
+                Ok(long(i64::MIN))
+            );
+        }
+
+        #[test]
+        fn negated_max_values() {
+            assert_eq!(
+                java::expression("-2147483647"),
+                Ok(unary(Operator::Minus, int(i32::MAX)))
+            );
+            assert_eq!(
+                java::expression("-9223372036854775807L"),
+                Ok(unary(Operator::Minus, long(i64::MAX)))
+            );
+        }
+
+        #[test]
+        fn min_value_magnitudes_without_unary_minus() {
+            assert!(java::expression("2147483648").is_err());
+            assert!(java::expression("9223372036854775808L").is_err());
+            assert!(java::expression("a - 2147483648").is_err());
+            assert!(java::expression("-(2147483648)").is_err());
+            assert!(java::expression("-2147483648L0").is_err());
+        }
+
+        #[test]
+        fn negated_min_value_in_binary_expression() {
+            assert_eq!(
+                java::expression("a * -2147483648"),
+                Ok(binary(Operator::Multiply, identifier("a"), int(i32::MIN)))
+            );
+        }
+
+        #[test]
+        fn parenthesized() {
+            assert_eq!(
+                java::expression("(1 + 2) * 3"),
+                Ok(binary(
+                    Operator::Multiply,
+                    binary(Operator::Plus, int(1), int(2)),
+                    int(3)
+                ))
+            );
+        }
+
+        #[test]
+        fn shifts_and_comparisons() {
+            assert_eq!(
+                java::expression("a << 1 < b >>> 2"),
+                Ok(binary(
+                    Operator::Less,
+                    binary(Operator::ShiftLeft, identifier("a"), int(1)),
+                    binary(Operator::UnsignedShiftRight, identifier("b"), int(2))
+                ))
+            );
+            assert_eq!(
+                java::expression("a >> 1 >= b"),
+                Ok(binary(
+                    Operator::GreaterOrEqual,
+                    binary(Operator::ShiftRight, identifier("a"), int(1)),
+                    identifier("b")
+                ))
+            );
+        }
+
+        #[test]
+        fn logical_and_bitwise() {
+            assert_eq!(
+                java::expression("a || b && c | d ^ e & f == g"),
+                Ok(binary(
+                    Operator::LogicalOr,
+                    identifier("a"),
+                    binary(
+                        Operator::LogicalAnd,
+                        identifier("b"),
+                        binary(
+                            Operator::BitwiseOr,
+                            identifier("c"),
+                            binary(
+                                Operator::BitwiseXor,
+                                identifier("d"),
+                                binary(
+                                    Operator::BitwiseAnd,
+                                    identifier("e"),
+                                    binary(Operator::Equal, identifier("f"), identifier("g"))
+                                )
+                            )
+                        )
+                    )
+                ))
+            );
+        }
+
+        #[test]
+        fn complements() {
+            assert_eq!(
+                java::expression("!a != ~b"),
+                Ok(binary(
+                    Operator::NotEqual,
+                    unary(Operator::LogicalComplement, identifier("a")),
+                    unary(Operator::BitwiseComplement, identifier("b"))
+                ))
+            );
+        }
+
+        #[test]
+        fn incomplete() {
+            assert!(java::expression("1 +").is_err());
+            assert!(java::expression("(1 + 2").is_err());
+        }
+    }
+
+    mod whitespace_and_comments {
+        use super::*;
+
+        #[test]
+        fn around_expression() {
+            assert_eq!(
+                java::expression(" 1 + 2 "),
+                Ok(binary(ast::Operator::Plus, int(1), int(2)))
+            );
+        }
+
+        #[test]
+        fn around_operators() {
+            assert_eq!(
+                java::expression("1  +\t2"),
+                Ok(binary(ast::Operator::Plus, int(1), int(2)))
+            );
+        }
+
+        #[test]
+        fn multiline_comment_around_dot() {
+            assert_eq!(
+                java::expression("a /*c*/ . b"),
+                Ok(field_access(identifier("a"), "b"))
+            );
+        }
+
+        #[test]
+        fn line_comments() {
+            assert_eq!(
+                java::expression("// leading\nf( a, // first\n b /* second */ ) // trailing"),
+                Ok(invocation(
+                    None,
+                    "f",
+                    vec![identifier("a"), identifier("b")]
+                ))
+            );
+        }
+
+        #[test]
+        fn comments_next_to_division() {
+            assert_eq!(
+                java::expression("a/*x*// /**/b"),
+                Ok(binary(
+                    ast::Operator::Divide,
+                    identifier("a"),
+                    identifier("b")
+                ))
+            );
+        }
+
+        #[test]
+        fn comments_inside_cast() {
+            assert_eq!(
+                java::expression("( /* type */ int ) x"),
+                Ok(cast(
+                    ast::Type::Primitive(ast::PrimitiveType::Int),
+                    identifier("x")
+                ))
+            );
+        }
+
+        #[test]
+        fn comments_between_array_dimensions() {
+            let expected = Ok(ast::Expression::NewArray {
+                element_type: ast::Type::Primitive(ast::PrimitiveType::Int),
+                dimensions: vec![Some(unspanned(int(3))), Some(unspanned(int(4))), None],
+            });
+            assert_eq!(java::expression("new int[3]/*c*/[4] /**/ []"), expected);
+            assert_eq!(java::expression("new int[3] // c\n [4][]"), expected);
+        }
+
+        #[test]
+        fn unterminated_comment() {
+            assert!(java::expression("a /* b").is_err());
+            assert!(java::expression("a /* omagad /*").is_err());
+        }
+
+        #[test]
+        fn empty_comment() {
+            assert_eq!(java::expression("/**/a/**/"), Ok(identifier("a")));
+        }
+
+        #[test]
+        fn comment_opening_inside_comment() {
+            assert_eq!(java::expression("/* a /* b */ c"), Ok(identifier("c")));
+        }
+
+        #[test]
+        fn comments_do_not_nest() {
+            assert!(java::expression("/* a /* b */ c */ d").is_err());
+        }
+    }
+
+    mod method_invocation {
+        use super::*;
+
+        #[test]
+        fn no_arguments() {
+            assert_eq!(
+                java::method_invocation_expression("foo()"),
+                Ok(invocation(None, "foo", vec![]))
+            );
+        }
+
+        #[test]
+        fn multiple_arguments() {
+            assert_eq!(
+                java::method_invocation_expression("foo( a ,b,\n 1 + 2 )"),
+                Ok(invocation(
+                    None,
+                    "foo",
+                    vec![
+                        identifier("a"),
+                        identifier("b"),
+                        binary(ast::Operator::Plus, int(1), int(2))
+                    ]
+                ))
+            );
+        }
+
+        #[test]
+        fn nested() {
+            assert_eq!(
+                java::expression("f(g(x))"),
+                Ok(invocation(
+                    None,
+                    "f",
+                    vec![invocation(None, "g", vec![identifier("x")])]
+                ))
+            );
+        }
+
+        #[test]
+        fn with_receiver() {
+            assert_eq!(
+                java::expression("obj.method(x)"),
+                Ok(invocation(
+                    Some(identifier("obj")),
+                    "method",
+                    vec![identifier("x")]
+                ))
+            );
+        }
+
+        #[test]
+        fn qualified_receiver() {
+            assert_eq!(
+                java::expression("System.out.println(x)"),
+                Ok(invocation(
+                    Some(field_access(identifier("System"), "out")),
+                    "println",
+                    vec![identifier("x")]
+                ))
+            );
+        }
+
+        #[test]
+        fn chained() {
+            assert_eq!(
+                java::expression("a().b(1, 2)"),
+                Ok(invocation(
+                    Some(invocation(None, "a", vec![])),
+                    "b",
+                    vec![int(1), int(2)]
+                ))
+            );
+        }
+
+        #[test]
+        fn in_operator_expression() {
+            assert_eq!(
+                java::expression("-f(1) * 2"),
+                Ok(binary(
+                    ast::Operator::Multiply,
+                    unary(ast::Operator::Minus, invocation(None, "f", vec![int(1)])),
+                    int(2)
+                ))
+            );
+        }
+
+        #[test]
+        fn malformed_arguments() {
+            assert!(java::expression("f(a,)").is_err());
+            assert!(java::expression("f(,a)").is_err());
+            assert!(java::expression("f(a b)").is_err());
+            assert!(java::expression("f(a").is_err());
+        }
+    }
+
+    mod array_access {
+        use super::*;
+
+        #[test]
+        fn simple() {
+            assert_eq!(
+                java::expression("a[i]"),
+                Ok(array_access(identifier("a"), identifier("i")))
+            );
+        }
+
+        #[test]
+        fn multi_dimensional() {
+            assert_eq!(
+                java::expression("matrix[0][1]"),
+                Ok(array_access(
+                    array_access(identifier("matrix"), int(0)),
+                    int(1)
+                ))
+            );
+        }
+
+        #[test]
+        fn complex_index() {
+            assert_eq!(
+                java::expression("a[ i + 1 ] * 2"),
+                Ok(binary(
+                    ast::Operator::Multiply,
+                    array_access(
+                        identifier("a"),
+                        binary(ast::Operator::Plus, identifier("i"), int(1))
+                    ),
+                    int(2)
+                ))
+            );
+        }
+
+        #[test]
+        fn of_invocation_result() {
+            assert_eq!(
+                java::expression("f()[0]"),
+                Ok(array_access(invocation(None, "f", vec![]), int(0)))
+            );
+        }
+
+        #[test]
+        fn missing_index() {
+            assert!(java::expression("a[]").is_err());
+            assert!(java::expression("a[1").is_err());
+        }
+    }
+
+    mod new_array {
+        use super::*;
+
+        #[test]
+        fn primitive() {
+            assert_eq!(
+                java::new_array_expression("new int[3]"),
+                Ok(ast::Expression::NewArray {
+                    element_type: ast::Type::Primitive(ast::PrimitiveType::Int),
+                    dimensions: vec![Some(unspanned(int(3)))],
+                })
+            );
+        }
+
+        #[test]
+        fn reference() {
+            assert_eq!(
+                java::new_array_expression("new String[n]"),
+                Ok(ast::Expression::NewArray {
+                    element_type: ast::Type::Reference(vec!["String".to_string()]),
+                    dimensions: vec![Some(unspanned(identifier("n")))],
+                })
+            );
+        }
+
+        #[test]
+        fn qualified_reference() {
+            assert_eq!(
+                java::new_array_expression("new java.util.Date[1]"),
+                Ok(ast::Expression::NewArray {
+                    element_type: ast::Type::Reference(vec![
+                        "java".to_string(),
+                        "util".to_string(),
+                        "Date".to_string()
+                    ]),
+                    dimensions: vec![Some(unspanned(int(1)))],
+                })
+            );
+        }
+
+        #[test]
+        fn sized_and_empty_dimensions() {
+            assert_eq!(
+                java::new_array_expression("new double [ 2 ] [n] [ ][]"),
+                Ok(ast::Expression::NewArray {
+                    element_type: ast::Type::Primitive(ast::PrimitiveType::Double),
+                    dimensions: vec![
+                        Some(unspanned(int(2))),
+                        Some(unspanned(identifier("n"))),
+                        None,
+                        None
+                    ],
+                })
+            );
+        }
+
+        #[test]
+        fn no_sized_dimensions() {
+            assert!(java::new_array_expression("new int[]").is_err());
+            assert!(java::new_array_expression("new int[][3]").is_err());
+            assert!(java::new_array_expression("new int[1][][2]").is_err());
+        }
+
+        #[test]
+        fn keyword_boundary() {
+            assert!(java::new_array_expression("newint[1]").is_err());
+        }
+    }
+
+    mod conditional {
+        use super::*;
+
+        #[test]
+        fn simple() {
+            assert_eq!(
+                java::expression("a ? b : c"),
+                Ok(conditional(
+                    identifier("a"),
+                    identifier("b"),
+                    identifier("c")
+                ))
+            );
+        }
+
+        #[test]
+        fn right_associative() {
+            assert_eq!(
+                java::expression("a ? b : c ? d : e"),
+                Ok(conditional(
+                    identifier("a"),
+                    identifier("b"),
+                    conditional(identifier("c"), identifier("d"), identifier("e"))
+                ))
+            );
+        }
+
+        #[test]
+        fn nested_in_then_branch() {
+            assert_eq!(
+                java::expression("a ? b ? c : d : e"),
+                Ok(conditional(
+                    identifier("a"),
+                    conditional(identifier("b"), identifier("c"), identifier("d")),
+                    identifier("e")
+                ))
+            );
+        }
+
+        #[test]
+        fn lowest_precedence() {
+            assert_eq!(
+                java::expression("a || b ? c + 1 : d * 2"),
+                Ok(conditional(
+                    binary(ast::Operator::LogicalOr, identifier("a"), identifier("b")),
+                    binary(ast::Operator::Plus, identifier("c"), int(1)),
+                    binary(ast::Operator::Multiply, identifier("d"), int(2))
+                ))
+            );
+        }
+
+        #[test]
+        fn missing_colon() {
+            assert!(java::expression("a ? b").is_err());
+            assert!(java::expression("a ? b :").is_err());
+        }
+    }
+
+    mod field_access {
+        use super::*;
+
+        #[test]
+        fn qualified_name() {
+            assert_eq!(
+                java::expression("java.lang.String"),
+                Ok(field_access(
+                    field_access(identifier("java"), "lang"),
+                    "String"
+                ))
+            );
+        }
+
+        #[test]
+        fn whitespace_around_dots() {
+            assert_eq!(
+                java::expression("a . b\n.c"),
+                Ok(field_access(field_access(identifier("a"), "b"), "c"))
+            );
+        }
+
+        #[test]
+        fn invocation_on_qualified_name() {
+            assert_eq!(
+                java::expression("a.b.c()"),
+                Ok(invocation(
+                    Some(field_access(identifier("a"), "b")),
+                    "c",
+                    vec![]
+                ))
+            );
+        }
+
+        #[test]
+        fn mixed_chain() {
+            assert_eq!(
+                java::expression("a.b().c.d(e.f)"),
+                Ok(invocation(
+                    Some(field_access(
+                        invocation(Some(identifier("a")), "b", vec![]),
+                        "c"
+                    )),
+                    "d",
+                    vec![field_access(identifier("e"), "f")]
+                ))
+            );
+        }
+
+        #[test]
+        fn of_array_element() {
+            assert_eq!(
+                java::expression("a[0].length"),
+                Ok(field_access(
+                    array_access(identifier("a"), int(0)),
+                    "length"
+                ))
+            );
+        }
+
+        #[test]
+        fn malformed() {
+            assert!(java::expression("a.").is_err());
+            assert!(java::expression("a..b").is_err());
+            assert!(java::expression("a.1").is_err());
+            assert!(java::expression("a.class").is_err());
+        }
+    }
+
+    mod instance_of {
+        use super::*;
+
+        fn instance_of(operand: ast::Expression, target_type: ast::Type) -> ast::Expression {
+            ast::Expression::InstanceOf {
+                operand: Box::new(unspanned(operand)),
+                target_type,
+            }
+        }
+
+        #[test]
+        fn simple() {
+            assert_eq!(
+                java::expression("a instanceof Foo"),
+                Ok(instance_of(
+                    identifier("a"),
+                    ast::Type::Reference(vec!["Foo".to_string()])
+                ))
+            );
+        }
+
+        #[test]
+        fn qualified_array_type() {
+            assert_eq!(
+                java::expression("a.b instanceof java.lang.String[]"),
+                Ok(instance_of(
+                    field_access(identifier("a"), "b"),
+                    ast::Type::Array(Box::new(ast::Type::Reference(vec![
+                        "java".to_string(),
+                        "lang".to_string(),
+                        "String".to_string()
+                    ])))
+                ))
+            );
+        }
+
+        #[test]
+        fn precedence() {
+            assert_eq!(
+                java::expression("a instanceof Foo && b == c instanceof Bar"),
+                Ok(binary(
+                    ast::Operator::LogicalAnd,
+                    instance_of(
+                        identifier("a"),
+                        ast::Type::Reference(vec!["Foo".to_string()])
+                    ),
+                    binary(
+                        ast::Operator::Equal,
+                        identifier("b"),
+                        instance_of(
+                            identifier("c"),
+                            ast::Type::Reference(vec!["Bar".to_string()])
+                        )
+                    )
+                ))
+            );
+        }
+
+        #[test]
+        fn keyword_boundary() {
+            assert_eq!(
+                java::expression("ainstanceof"),
+                Ok(identifier("ainstanceof"))
+            );
+            assert!(java::expression("a instanceofFoo").is_err());
+        }
+
+        #[test]
+        fn primitive_type() {
+            assert!(java::expression("a instanceof int").is_err());
+        }
+    }
+
+    mod cast {
+        use super::*;
+
+        #[test]
+        fn array() {
+            assert_eq!(
+                java::expression("(int[]) x"),
+                Ok(cast(
+                    ast::Type::Array(Box::new(ast::Type::Primitive(ast::PrimitiveType::Int))),
+                    identifier("x")
+                ))
+            );
+            assert_eq!(
+                java::expression("(String [][])x"),
+                Ok(cast(
+                    ast::Type::Array(Box::new(ast::Type::Array(Box::new(super::reference(
+                        "String"
+                    ))))),
+                    identifier("x")
+                ))
+            );
+        }
+
+        #[test]
+        fn array_of_negation() {
+            assert!(java::expression("(int[]) -x").is_err());
+        }
+
+        #[test]
+        fn primitive() {
+            assert_eq!(
+                java::expression("(long) 5"),
+                Ok(cast(ast::Type::Primitive(ast::PrimitiveType::Long), int(5)))
+            );
+        }
+
+        #[test]
+        fn primitive_of_negation() {
+            assert_eq!(
+                java::expression("(int)-x"),
+                Ok(cast(
+                    ast::Type::Primitive(ast::PrimitiveType::Int),
+                    unary(ast::Operator::Minus, identifier("x"))
+                ))
+            );
+        }
+
+        #[test]
+        fn reference() {
+            assert_eq!(
+                java::expression("(MyType) obj"),
+                Ok(cast(
+                    ast::Type::Reference(vec!["MyType".to_string()]),
+                    identifier("obj")
+                ))
+            );
+        }
+
+        #[test]
+        fn qualified_reference() {
+            assert_eq!(
+                java::expression("( java.lang.Object )(x)"),
+                Ok(cast(
+                    ast::Type::Reference(vec![
+                        "java".to_string(),
+                        "lang".to_string(),
+                        "Object".to_string()
+                    ]),
+                    identifier("x")
+                ))
+            );
+        }
+
+        #[test]
+        fn binds_tighter_than_binary_operators() {
+            assert_eq!(
+                java::expression("(double) a / b.c"),
+                Ok(binary(
+                    ast::Operator::Divide,
+                    cast(
+                        ast::Type::Primitive(ast::PrimitiveType::Double),
+                        identifier("a")
+                    ),
+                    field_access(identifier("b"), "c")
+                ))
+            );
+        }
+
+        #[test]
+        fn parenthesized_minus() {
+            assert_eq!(
+                java::expression("(x) - y"),
+                Ok(binary(
+                    ast::Operator::Minus,
+                    identifier("x"),
+                    identifier("y")
+                ))
+            );
+        }
+
+        #[test]
+        fn parenthesized_plus() {
+            assert_eq!(
+                java::expression("(a) + b"),
+                Ok(binary(
+                    ast::Operator::Plus,
+                    identifier("a"),
+                    identifier("b")
+                ))
+            );
+        }
+
+        #[test]
+        fn parenthesized_only() {
+            assert_eq!(java::expression("(a)"), Ok(identifier("a")));
+            assert_eq!(
+                java::expression("(a)[0]"),
+                Ok(array_access(identifier("a"), int(0)))
+            );
+        }
+
+        #[test]
+        fn missing_operand() {
+            assert!(java::expression("(int)").is_err());
+            assert!(java::expression("(int) + ").is_err());
+        }
+    }
+
+    mod statement {
+        use super::*;
+
+        #[test]
+        fn local_variable_with_initializer() {
+            assert_eq!(
+                java::statement("int x = 1 + 2;"),
+                Ok(local_variable(
+                    ast::Type::Primitive(ast::PrimitiveType::Int),
+                    "x",
+                    Some(binary(ast::Operator::Plus, int(1), int(2)))
+                ))
+            );
+        }
+
+        #[test]
+        fn local_variable_without_initializer() {
+            assert_eq!(
+                java::statement("java.lang.String s ;"),
+                Ok(local_variable(reference("java.lang.String"), "s", None))
+            );
+        }
+
+        #[test]
+        fn local_variable_of_array_type() {
+            assert_eq!(
+                java::statement("int[] [] a = new int[2][];"),
+                Ok(local_variable(
+                    ast::Type::Array(Box::new(ast::Type::Array(Box::new(ast::Type::Primitive(
+                        ast::PrimitiveType::Int
+                    ))))),
+                    "a",
+                    Some(ast::Expression::NewArray {
+                        element_type: ast::Type::Primitive(ast::PrimitiveType::Int),
+                        dimensions: vec![Some(unspanned(int(2))), None],
+                    })
+                ))
+            );
+        }
+
+        #[test]
+        fn expression_statement() {
+            assert_eq!(
+                java::statement("System.out.println(x);"),
+                Ok(ast::Statement::Expression(unspanned(invocation(
+                    Some(field_access(identifier("System"), "out")),
+                    "println",
+                    vec![identifier("x")]
+                ))))
+            );
+        }
+
+        #[test]
+        fn not_a_statement_expression() {
+            assert!(java::statement("1 + 2;").is_err());
+            assert!(java::statement("a;").is_err());
+        }
+
+        #[test]
+        fn block() {
+            assert_eq!(
+                java::statement("{\n    int a = 1; // one\n    foo(a);\n    {}\n}"),
+                Ok(ast::Statement::Block(vec![
+                    local_variable(
+                        ast::Type::Primitive(ast::PrimitiveType::Int),
+                        "a",
+                        Some(int(1))
+                    ),
+                    ast::Statement::Expression(unspanned(invocation(
+                        None,
+                        "foo",
+                        vec![identifier("a")]
+                    ))),
+                    ast::Statement::Block(vec![]),
+                ]))
+            );
+        }
+
+        #[test]
+        fn missing_semicolon() {
+            assert!(java::statement("int x = 1").is_err());
+            assert!(java::statement("foo()").is_err());
+            assert!(java::statement("{ foo() }").is_err());
+        }
+
+        #[test]
+        fn unterminated_block() {
+            assert!(java::statement("{ foo();").is_err());
+        }
+
+        fn call(name: &str) -> ast::Statement {
+            ast::Statement::Expression(unspanned(invocation(None, name, vec![])))
+        }
+
+        #[test]
+        fn if_without_else() {
+            assert_eq!(
+                java::statement("if (a) foo();"),
+                Ok(ast::Statement::If {
+                    condition: unspanned(identifier("a")),
+                    then_branch: Box::new(call("foo")),
+                    else_branch: None,
+                })
+            );
+        }
+
+        #[test]
+        fn if_else_chain() {
+            assert_eq!(
+                java::statement("if (a) foo(); else if (b) bar(); else { baz(); }"),
+                Ok(ast::Statement::If {
+                    condition: unspanned(identifier("a")),
+                    then_branch: Box::new(call("foo")),
+                    else_branch: Some(Box::new(ast::Statement::If {
+                        condition: unspanned(identifier("b")),
+                        then_branch: Box::new(call("bar")),
+                        else_branch: Some(Box::new(ast::Statement::Block(vec![call("baz")]))),
+                    })),
+                })
+            );
+        }
+
+        #[test]
+        fn dangling_else_binds_to_nearest_if() {
+            assert_eq!(
+                java::statement("if (a) if (b) foo(); else bar();"),
+                Ok(ast::Statement::If {
+                    condition: unspanned(identifier("a")),
+                    then_branch: Box::new(ast::Statement::If {
+                        condition: unspanned(identifier("b")),
+                        then_branch: Box::new(call("foo")),
+                        else_branch: Some(Box::new(call("bar"))),
+                    }),
+                    else_branch: None,
+                })
+            );
+        }
+
+        #[test]
+        fn while_loop() {
+            assert_eq!(
+                java::statement("while (i < 10) { int j = i; foo(); }"),
+                Ok(ast::Statement::While {
+                    condition: unspanned(binary(ast::Operator::Less, identifier("i"), int(10))),
+                    body: Box::new(ast::Statement::Block(vec![
+                        local_variable(
+                            ast::Type::Primitive(ast::PrimitiveType::Int),
+                            "j",
+                            Some(identifier("i"))
+                        ),
+                        call("foo"),
+                    ])),
+                })
+            );
+        }
+
+        #[test]
+        fn condition_requires_parentheses() {
+            assert!(java::statement("if a foo();").is_err());
+            assert!(java::statement("while a foo();").is_err());
+            assert!(java::statement("ifx (a) foo();").is_err());
+        }
+
+        #[test]
+        fn return_value() {
+            assert_eq!(
+                java::statement("return 1 + 2;"),
+                Ok(ast::Statement::Return(Some(unspanned(binary(
+                    ast::Operator::Plus,
+                    int(1),
+                    int(2)
+                )))))
+            );
+            assert_eq!(
+                java::statement("return(a);"),
+                Ok(ast::Statement::Return(Some(unspanned(identifier("a")))))
+            );
+        }
+
+        #[test]
+        fn return_void() {
+            assert_eq!(
+                java::statement("return ;"),
+                Ok(ast::Statement::Return(None))
+            );
+        }
+
+        #[test]
+        fn break_and_continue() {
+            assert_eq!(java::statement("break;"), Ok(ast::Statement::Break(None)));
+            assert_eq!(
+                java::statement("break outer;"),
+                Ok(ast::Statement::Break(Some("outer".to_string())))
+            );
+            assert_eq!(
+                java::statement("continue;"),
+                Ok(ast::Statement::Continue(None))
+            );
+            assert_eq!(
+                java::statement("continue /* label */ outer ;"),
+                Ok(ast::Statement::Continue(Some("outer".to_string())))
+            );
+        }
+
+        #[test]
+        fn jump_requires_semicolon() {
+            assert!(java::statement("return 1").is_err());
+            assert!(java::statement("break outer").is_err());
+            assert!(java::statement("continue").is_err());
+            assert!(java::statement("break 1;").is_err());
+        }
+
+        #[test]
+        fn keyword_as_variable_name() {
+            assert!(java::statement("int class = 1;").is_err());
+        }
+    }
+
+    mod annotation_type {
+        use super::*;
+
+        fn element(
+            element_type: ast::Type,
+            name: &str,
+            default_value: Option<ast::ElementValue>,
+        ) -> ast::AnnotationTypeElement {
+            ast::AnnotationTypeElement {
+                annotations: vec![],
+                modifiers: vec![],
+                element_type,
+                name: name.to_string(),
+                default_value,
+            }
+        }
+
+        fn declaration(
+            name: &str,
+            elements: Vec<ast::AnnotationTypeElement>,
+        ) -> ast::AnnotationTypeDeclaration {
+            ast::AnnotationTypeDeclaration {
+                annotations: vec![],
+                modifiers: vec![],
+                name: name.to_string(),
+                elements,
+            }
+        }
+
+        fn value(expression: ast::Expression) -> ast::ElementValue {
+            ast::ElementValue::Expression(unspanned(expression))
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!(
+                java::annotation_type_declaration("@interface Marker {}"),
+                Ok(ast::AnnotationTypeDeclaration {
+                    annotations: vec![],
+                    modifiers: vec![],
+                    name: "Marker".to_string(),
+                    elements: vec![],
+                })
+            );
+        }
+
+        #[test]
+        fn elements() {
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "public @ interface Foo {\n    int value();\n    String[] names() default null;\n}"
+                ),
+                Ok(ast::AnnotationTypeDeclaration {
+                    annotations: vec![],
+                    modifiers: vec![ast::Modifier::Public],
+                    name: "Foo".to_string(),
+                    elements: vec![
+                        element(ast::Type::Primitive(ast::PrimitiveType::Int), "value", None),
+                        element(
+                            ast::Type::Array(Box::new(reference("String"))),
+                            "names",
+                            Some(value(ast::Expression::Literal(ast::Literal::Null)))
+                        ),
+                    ],
+                })
+            );
+        }
+
+        #[test]
+        fn defaulted_element() {
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@interface Retry { long delay() default 10 * 1000L; }"
+                ),
+                Ok(declaration(
+                    "Retry",
+                    vec![element(
+                        ast::Type::Primitive(ast::PrimitiveType::Long),
+                        "delay",
+                        Some(value(binary(ast::Operator::Multiply, int(10), long(1000))))
+                    )]
+                ))
+            );
+        }
+
+        #[test]
+        fn array_default() {
+            let int_array =
+                || ast::Type::Array(Box::new(ast::Type::Primitive(ast::PrimitiveType::Int)));
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@interface Foo { int[] v() default {1, 2}; int[] w() default { 3, }; int[] e() default {}; }"
+                ),
+                Ok(declaration(
+                    "Foo",
+                    vec![
+                        element(
+                            int_array(),
+                            "v",
+                            Some(ast::ElementValue::Array(vec![value(int(1)), value(int(2))]))
+                        ),
+                        element(
+                            int_array(),
+                            "w",
+                            Some(ast::ElementValue::Array(vec![value(int(3))]))
+                        ),
+                        element(int_array(), "e", Some(ast::ElementValue::Array(vec![]))),
+                    ]
+                ))
+            );
+        }
+
+        #[test]
+        fn annotation_default() {
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@interface Foo { Bar bar() default @Bar; Baz baz() default @x.Baz(2); \
+                     Qux qux() default @Qux(a = 1, b = {@Bar()}); }"
+                ),
+                Ok(declaration(
+                    "Foo",
+                    vec![
+                        element(
+                            reference("Bar"),
+                            "bar",
+                            Some(ast::ElementValue::Annotation(ast::Annotation {
+                                type_name: vec!["Bar".to_string()],
+                                elements: vec![],
+                            }))
+                        ),
+                        element(
+                            reference("Baz"),
+                            "baz",
+                            Some(ast::ElementValue::Annotation(ast::Annotation {
+                                type_name: vec!["x".to_string(), "Baz".to_string()],
+                                elements: vec![ast::ElementValuePair {
+                                    name: "value".to_string(),
+                                    value: value(int(2)),
+                                }],
+                            }))
+                        ),
+                        element(
+                            reference("Qux"),
+                            "qux",
+                            Some(ast::ElementValue::Annotation(ast::Annotation {
+                                type_name: vec!["Qux".to_string()],
+                                elements: vec![
+                                    ast::ElementValuePair {
+                                        name: "a".to_string(),
+                                        value: value(int(1)),
+                                    },
+                                    ast::ElementValuePair {
+                                        name: "b".to_string(),
+                                        value: ast::ElementValue::Array(vec![
+                                            ast::ElementValue::Annotation(ast::Annotation {
+                                                type_name: vec!["Bar".to_string()],
+                                                elements: vec![],
+                                            })
+                                        ]),
+                                    },
+                                ],
+                            }))
+                        ),
+                    ]
+                ))
+            );
+        }
+
+        #[test]
+        fn modified_element() {
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@interface Foo { public abstract int value(); }"
+                ),
+                Ok(declaration(
+                    "Foo",
+                    vec![ast::AnnotationTypeElement {
+                        annotations: vec![],
+                        modifiers: vec![ast::Modifier::Public, ast::Modifier::Abstract],
+                        element_type: ast::Type::Primitive(ast::PrimitiveType::Int),
+                        name: "value".to_string(),
+                        default_value: None,
+                    }]
+                ))
+            );
+        }
+
+        fn marker(name: &str) -> ast::Annotation {
+            ast::Annotation {
+                type_name: vec![name.to_string()],
+                elements: vec![],
+            }
+        }
+
+        #[test]
+        fn annotated_interface() {
+            let error = java::annotation_type_declaration("@Foo interface Bar {}").unwrap_err();
+            assert_eq!(error.location.column, 6);
+            assert!(error.expected.tokens().any(|token| token == "\"@\""));
+            assert!(java::annotation_type_declaration("@interfaceFoo {}").is_err());
+        }
+
+        #[test]
+        fn annotated_annotation_type() {
+            assert_eq!(
+                java::annotation_type_declaration("@Foo @interface Bar {}"),
+                Ok(ast::AnnotationTypeDeclaration {
+                    annotations: vec![marker("Foo")],
+                    ..declaration("Bar", vec![])
+                })
+            );
+            assert_eq!(
+                java::annotation_type_declaration("@Retention(RUNTIME) @interface Foo {}"),
+                Ok(ast::AnnotationTypeDeclaration {
+                    annotations: vec![ast::Annotation {
+                        type_name: vec!["Retention".to_string()],
+                        elements: vec![ast::ElementValuePair {
+                            name: "value".to_string(),
+                            value: value(identifier("RUNTIME")),
+                        }],
+                    }],
+                    ..declaration("Foo", vec![])
+                })
+            );
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@Documented public @Inherited @interface Foo {}"
+                ),
+                Ok(ast::AnnotationTypeDeclaration {
+                    annotations: vec![marker("Documented"), marker("Inherited")],
+                    modifiers: vec![ast::Modifier::Public],
+                    ..declaration("Foo", vec![])
+                })
+            );
+        }
+
+        #[test]
+        fn annotated_element() {
+            assert_eq!(
+                java::annotation_type_declaration(
+                    "@interface Foo { @Deprecated public int x(); @java.lang.Deprecated int y(); }"
+                ),
+                Ok(declaration(
+                    "Foo",
+                    vec![
+                        ast::AnnotationTypeElement {
+                            annotations: vec![marker("Deprecated")],
+                            modifiers: vec![ast::Modifier::Public],
+                            ..element(ast::Type::Primitive(ast::PrimitiveType::Int), "x", None)
+                        },
+                        ast::AnnotationTypeElement {
+                            annotations: vec![ast::Annotation {
+                                type_name: vec![
+                                    "java".to_string(),
+                                    "lang".to_string(),
+                                    "Deprecated".to_string()
+                                ],
+                                elements: vec![],
+                            }],
+                            ..element(ast::Type::Primitive(ast::PrimitiveType::Int), "y", None)
+                        },
+                    ]
+                ))
+            );
+        }
+
+        #[test]
+        fn missing_semicolon() {
+            assert!(java::annotation_type_declaration("@interface Foo { int value() }").is_err());
+        }
+    }
+}
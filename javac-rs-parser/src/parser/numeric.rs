@@ -0,0 +1,219 @@
+//! Conversions of numeric literals' source representation into their values
+
+use std::convert::TryFrom;
+
+/// Digit separator allowed between digits of numeric literals.
+const DIGIT_SEPARATOR: char = '_';
+
+/// Removes all digit separators from the given digits.
+fn strip_separators(digits: &str) -> String {
+    digits.replace(DIGIT_SEPARATOR, "")
+}
+
+/// Computes the value of an `int` literal given its digits and radix.
+///
+/// Non-decimal literals may represent any 32-bit pattern
+/// while decimal ones are limited by `i32::MAX`.
+pub(super) fn int_value(digits: &str, radix: u32) -> Option<i32> {
+    let value = u32::from_str_radix(&strip_separators(digits), radix).ok()?;
+    if radix == 10 {
+        i32::try_from(value).ok()
+    } else {
+        Some(value as i32)
+    }
+}
+
+/// Computes the value of a `long` literal given its digits and radix.
+///
+/// Non-decimal literals may represent any 64-bit pattern
+/// while decimal ones are limited by `i64::MAX`.
+pub(super) fn long_value(digits: &str, radix: u32) -> Option<i64> {
+    let value = u64::from_str_radix(&strip_separators(digits), radix).ok()?;
+    if radix == 10 {
+        i64::try_from(value).ok()
+    } else {
+        Some(value as i64)
+    }
+}
+
+/// Checks whether the decimal digits denote the magnitude of `i32::MIN`
+/// which may only appear as the operand of unary minus.
+pub(super) fn is_int_min_magnitude(digits: &str) -> bool {
+    strip_separators(digits) == i32::MIN.unsigned_abs().to_string()
+}
+
+/// Checks whether the decimal digits denote the magnitude of `i64::MIN`
+/// which may only appear as the operand of unary minus.
+pub(super) fn is_long_min_magnitude(digits: &str) -> bool {
+    strip_separators(digits) == i64::MIN.unsigned_abs().to_string()
+}
+
+/// Source representation of a floating-point literal without its type suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FloatingPoint<'a> {
+    /// Decimal floating-point number, e.g. `1.5e10`
+    Decimal(&'a str),
+    /// Hexadecimal floating-point number, e.g. `0x1.8p1`
+    Hexadecimal {
+        /// Significand digits possibly containing a point, e.g. `1.8`
+        significand: &'a str,
+        /// Optionally signed decimal binary exponent, e.g. `-1`
+        exponent: &'a str,
+    },
+}
+
+/// Parameters of an IEEE 754 binary floating-point format.
+struct BinaryFormat {
+    /// Number of significand bits including the implicit one
+    precision: u32,
+    /// Minimal exponent of a normal value
+    min_exponent: i64,
+    /// Maximal exponent of a finite value
+    max_exponent: i64,
+}
+
+const BINARY32: BinaryFormat = BinaryFormat {
+    precision: 24,
+    min_exponent: -126,
+    max_exponent: 127,
+};
+
+const BINARY64: BinaryFormat = BinaryFormat {
+    precision: 53,
+    min_exponent: -1022,
+    max_exponent: 1023,
+};
+
+impl FloatingPoint<'_> {
+    /// Computes the value of this number as a `float`
+    /// failing if it is too large or if it is non-zero but gets rounded to zero.
+    pub(super) fn to_f32(self) -> Option<f32> {
+        match self {
+            Self::Decimal(number) => {
+                let number = strip_separators(number);
+                number
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|value| is_representable(*value == 0.0, value.is_finite(), &number))
+            }
+            // the value is exactly representable as `f32` after rounding
+            Self::Hexadecimal {
+                significand,
+                exponent,
+            } => hex_value(significand, exponent, &BINARY32).map(|value| value as f32),
+        }
+    }
+
+    /// Computes the value of this number as a `double`
+    /// failing if it is too large or if it is non-zero but gets rounded to zero.
+    pub(super) fn to_f64(self) -> Option<f64> {
+        match self {
+            Self::Decimal(number) => {
+                let number = strip_separators(number);
+                number
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|value| is_representable(*value == 0.0, value.is_finite(), &number))
+            }
+            Self::Hexadecimal {
+                significand,
+                exponent,
+            } => hex_value(significand, exponent, &BINARY64),
+        }
+    }
+}
+
+/// Checks that the parsed decimal number is neither infinite nor a non-zero number rounded to zero.
+fn is_representable(is_zero: bool, is_finite: bool, number: &str) -> bool {
+    is_finite
+        && !(is_zero
+            && number
+                .chars()
+                .take_while(|symbol| !matches!(symbol, 'e' | 'E'))
+                .any(|symbol| matches!(symbol, '1'..='9')))
+}
+
+/// Computes the value of a hexadecimal floating-point number
+/// correctly rounded (to nearest, ties to even) to the given format.
+fn hex_value(significand: &str, exponent: &str, format: &BinaryFormat) -> Option<f64> {
+    // Bound of exponent's magnitude beyond which any non-zero value overflows or underflows
+    const EXPONENT_BOUND: i64 = 1 << 20;
+
+    let exponent = exponent
+        .replace(DIGIT_SEPARATOR, "")
+        .parse::<i64>()
+        .unwrap_or_else(|_| {
+            if exponent.starts_with('-') {
+                -EXPONENT_BOUND
+            } else {
+                EXPONENT_BOUND
+            }
+        })
+        .clamp(-EXPONENT_BOUND, EXPONENT_BOUND);
+
+    // the value is `bits * 2^exponent` with `sticky` marking any non-zero bits dropped
+    let mut bits = 0_u64;
+    let mut sticky = false;
+    let mut exponent = exponent;
+    let mut after_point = false;
+    for symbol in significand.chars() {
+        if symbol == '.' {
+            after_point = true;
+            continue;
+        }
+        let digit = match symbol.to_digit(16) {
+            Some(digit) => u64::from(digit),
+            None => continue,
+        };
+        if bits >> 60 == 0 {
+            bits = bits << 4 | digit;
+            if after_point {
+                exponent -= 4;
+            }
+        } else {
+            sticky |= digit != 0;
+            if !after_point {
+                exponent += 4;
+            }
+        }
+    }
+
+    if bits == 0 {
+        return Some(0.0);
+    }
+
+    let length = i64::from(64 - bits.leading_zeros());
+    // exponent of the most significant bit
+    let top_exponent = exponent + length - 1;
+    let precision = i64::from(format.precision)
+        - if top_exponent < format.min_exponent {
+            format.min_exponent - top_exponent
+        } else {
+            0
+        };
+
+    if precision < 0 || precision == 0 && bits.is_power_of_two() && !sticky {
+        // rounded to zero
+        return None;
+    }
+
+    let shift = length - precision;
+    if shift > 0 {
+        // shift may be up to 64 so wider type is used
+        let dropped = u128::from(bits) & ((1_u128 << shift) - 1);
+        let half = 1_u128 << (shift - 1);
+        bits = (u128::from(bits) >> shift) as u64;
+        exponent += shift;
+        if dropped > half || dropped == half && (sticky || bits & 1 == 1) {
+            bits += 1;
+        }
+    }
+
+    if exponent + i64::from(64 - bits.leading_zeros()) - 1 > format.max_exponent {
+        return None;
+    }
+
+    // split the scaling so that neither of the factors underflows
+    let exponent = exponent as i32;
+    Some(bits as f64 * 2_f64.powi(exponent / 2) * 2_f64.powi(exponent - exponent / 2))
+}
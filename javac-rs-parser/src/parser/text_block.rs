@@ -0,0 +1,103 @@
+//! Processing of text blocks' content as specified by JLS 3.10.6
+
+/// Checks whether the symbol is white space as defined by `Character.isWhitespace(char)`.
+fn is_whitespace(symbol: char) -> bool {
+    symbol.is_whitespace() && !matches!(symbol, '\u{A0}' | '\u{2007}' | '\u{202F}')
+}
+
+fn is_blank(line: &str) -> bool {
+    line.chars().all(is_whitespace)
+}
+
+/// Normalizes line terminators to `\n` and strips incidental white space
+/// from the raw content of a text block, i.e. the part between the line terminator
+/// following the opening delimiter and the closing delimiter.
+pub(super) fn strip_indent(content: &str) -> String {
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
+    let lines = content.split('\n').collect::<Vec<_>>();
+
+    // the last line is significant even if it is blank as it positions the closing delimiter
+    let last = lines.len() - 1;
+    let indentation = lines
+        .iter()
+        .enumerate()
+        .filter(|(index, line)| *index == last || !is_blank(line))
+        .map(|(_, line)| {
+            line.chars()
+                .take_while(|symbol| is_whitespace(*symbol))
+                .count()
+        })
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| {
+            if is_blank(line) {
+                ""
+            } else {
+                let start = line
+                    .char_indices()
+                    .nth(indentation)
+                    .map_or(line.len(), |(index, _)| index);
+                line[start..].trim_end_matches(is_whitespace)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Interprets escape sequences in the stripped content of a text block
+/// including `\<line-terminator>` which joins the line with the following one.
+pub(super) fn translate_escapes(content: &str) -> Option<Vec<u16>> {
+    let mut units = Vec::with_capacity(content.len());
+    let mut symbols = content.chars().peekable();
+    while let Some(symbol) = symbols.next() {
+        if symbol != '\\' {
+            let mut buffer = [0; 2];
+            units.extend_from_slice(symbol.encode_utf16(&mut buffer));
+            continue;
+        }
+
+        let unit = match symbols.next()? {
+            'b' => 0x08,
+            't' => 0x09,
+            'n' => 0x0A,
+            'f' => 0x0C,
+            'r' => 0x0D,
+            's' => 0x20,
+            '"' => 0x22,
+            '\'' => 0x27,
+            '\\' => 0x5C,
+            '\n' => continue,
+            'u' => {
+                while symbols.peek() == Some(&'u') {
+                    symbols.next();
+                }
+                let mut value = 0;
+                for _ in 0..4 {
+                    value = value << 4 | symbols.next()?.to_digit(16)?;
+                }
+                value as u16
+            }
+            first @ '0'..='7' => {
+                let max_length = if first <= '3' { 3 } else { 2 };
+                let mut value = first.to_digit(8)?;
+                for _ in 1..max_length {
+                    match symbols.peek().and_then(|symbol| symbol.to_digit(8)) {
+                        Some(digit) => {
+                            value = value << 3 | digit;
+                            symbols.next();
+                        }
+                        None => break,
+                    }
+                }
+                value as u16
+            }
+            _ => return None,
+        };
+        units.push(unit);
+    }
+
+    Some(units)
+}